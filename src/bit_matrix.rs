@@ -0,0 +1,285 @@
+use cargo_snippet::snippet;
+
+/// Matrix over GF(2) storing each row as `u64` words.
+#[snippet("BitMatrix")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    h: usize,
+    w: usize,
+    words: usize,
+    v: Vec<u64>,
+}
+
+#[snippet("BitMatrix")]
+impl BitMatrix {
+    pub fn new(h: usize, w: usize) -> Self {
+        let words = w.div_ceil(64);
+        Self {
+            h,
+            w,
+            words,
+            v: vec![0; h * words],
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        assert!(i < self.h && j < self.w);
+        self.v[i * self.words + j / 64] >> (j % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, b: bool) {
+        assert!(i < self.h && j < self.w);
+        let x = &mut self.v[i * self.words + j / 64];
+        if b {
+            *x |= 1 << (j % 64);
+        } else {
+            *x &= !(1 << (j % 64));
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for k in 0..self.words {
+            self.v.swap(a * self.words + k, b * self.words + k);
+        }
+    }
+
+    /// Gauss-Jordan elimination on the first `cols` columns. Returns the pivot columns.
+    fn gauss_jordan(&mut self, cols: usize) -> Vec<usize> {
+        let mut pivots = vec![];
+        for c in 0..cols {
+            let r = pivots.len();
+            if r == self.h {
+                break;
+            }
+            let p = match (r..self.h).find(|&i| self.get(i, c)) {
+                Some(p) => p,
+                None => continue,
+            };
+            self.swap_rows(p, r);
+            for i in 0..self.h {
+                if i != r && self.get(i, c) {
+                    for k in c / 64..self.words {
+                        self.v[i * self.words + k] ^= self.v[r * self.words + k];
+                    }
+                }
+            }
+            pivots.push(c);
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().gauss_jordan(self.w).len()
+    }
+
+    pub fn determinant(&self) -> bool {
+        assert_eq!(self.h, self.w);
+        self.rank() == self.h
+    }
+
+    fn augment(&self, b: &[bool]) -> Self {
+        let mut a = BitMatrix::new(self.h, self.w + 1);
+        for i in 0..self.h {
+            for j in 0..self.w {
+                a.set(i, j, self.get(i, j));
+            }
+            a.set(i, self.w, b[i]);
+        }
+        a
+    }
+
+    /// Returns a solution of `self * x = b`, or `None` if there is none.
+    pub fn solve(&self, b: &[bool]) -> Option<Vec<bool>> {
+        assert_eq!(self.h, b.len());
+        let mut a = self.augment(b);
+        let pivots = a.gauss_jordan(self.w);
+        if (pivots.len()..self.h).any(|i| a.get(i, self.w)) {
+            return None;
+        }
+        let mut x = vec![false; self.w];
+        for (r, &c) in pivots.iter().enumerate() {
+            x[c] = a.get(r, self.w);
+        }
+        Some(x)
+    }
+
+    /// Basis of the solutions of `self * x = 0`.
+    pub fn kernel(&self) -> Vec<Vec<bool>> {
+        let mut a = self.clone();
+        let pivots = a.gauss_jordan(self.w);
+        let mut is_pivot = vec![false; self.w];
+        for &c in &pivots {
+            is_pivot[c] = true;
+        }
+        (0..self.w)
+            .filter(|&f| !is_pivot[f])
+            .map(|f| {
+                let mut k = vec![false; self.w];
+                k[f] = true;
+                for (r, &c) in pivots.iter().enumerate() {
+                    k[c] = a.get(r, f);
+                }
+                k
+            })
+            .collect()
+    }
+}
+
+/// Linear basis of `u64` values under xor, kept in reduced row echelon form.
+#[snippet("XorBasis")]
+#[derive(Debug, Clone, Default)]
+pub struct XorBasis {
+    basis: Vec<u64>,
+}
+
+#[snippet("XorBasis")]
+impl XorBasis {
+    pub fn new() -> Self {
+        Self { basis: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.basis.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.basis.is_empty()
+    }
+
+    fn reduce(&self, mut x: u64) -> u64 {
+        for &b in &self.basis {
+            x = x.min(x ^ b);
+        }
+        x
+    }
+
+    /// Returns `false` if `x` is already spanned by the basis.
+    pub fn insert(&mut self, x: u64) -> bool {
+        let x = self.reduce(x);
+        if x == 0 {
+            return false;
+        }
+        let top = 63 - x.leading_zeros();
+        for b in self.basis.iter_mut() {
+            if *b >> top & 1 == 1 {
+                *b ^= x;
+            }
+        }
+        self.basis.push(x);
+        self.basis.sort_unstable();
+        true
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        self.reduce(x) == 0
+    }
+
+    /// Maximum of `x ^ y` over `y` in the span.
+    pub fn max_xor(&self, x: u64) -> u64 {
+        self.basis.iter().rev().fold(x, |x, &b| x.max(x ^ b))
+    }
+
+    /// `k`-th smallest (0-indexed) distinct value in the span, including 0.
+    pub fn kth(&self, k: u64) -> Option<u64> {
+        if self.basis.len() < 64 && k >> self.basis.len() != 0 {
+            return None;
+        }
+        Some(
+            (0..self.basis.len())
+                .filter(|&i| k >> i & 1 == 1)
+                .fold(0, |x, i| x ^ self.basis[i]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitMatrix, XorBasis};
+    use crate::matrix::Matrix;
+    use crate::modint::ModInt;
+    use rand::prelude::*;
+
+    type Mod2 = ModInt<2>;
+
+    fn random_matrix(rng: &mut ThreadRng, h: usize, w: usize) -> (BitMatrix, Matrix<Mod2>) {
+        let mut a = BitMatrix::new(h, w);
+        let mut v = vec![vec![Mod2::new(0); w]; h];
+        for i in 0..h {
+            for j in 0..w {
+                let b = rng.gen_bool(0.3);
+                a.set(i, j, b);
+                v[i][j] = Mod2::new(b as i64);
+            }
+        }
+        (a, Matrix::new(v))
+    }
+
+    #[test]
+    fn test_bit_matrix() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let (h, w) = (rng.gen_range(1..50), rng.gen_range(1..100));
+            let (a, m) = random_matrix(&mut rng, h, w);
+            assert_eq!(a.rank(), m.rank());
+
+            let b = (0..h).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>();
+            let mul = |x: &[bool]| {
+                (0..h)
+                    .map(|i| (0..w).filter(|&j| a.get(i, j) && x[j]).count() % 2 == 1)
+                    .collect::<Vec<_>>()
+            };
+            let expected = m.solve(&b.iter().map(|&b| Mod2::new(b as i64)).collect::<Vec<_>>());
+            match a.solve(&b) {
+                Some(x) => assert_eq!(mul(&x), b),
+                None => assert!(expected.is_none()),
+            }
+            let kernel = a.kernel();
+            assert_eq!(kernel.len(), w - a.rank());
+            for k in kernel {
+                assert!(mul(&k).iter().all(|&b| !b));
+            }
+
+            let (a, m) = random_matrix(&mut rng, h, h);
+            assert_eq!(a.determinant(), m.determinant() == Mod2::new(1));
+        }
+    }
+
+    #[test]
+    fn test_xor_basis() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let mut basis = XorBasis::new();
+            let mut span = vec![0u64];
+            for _ in 0..rng.gen_range(0..8) {
+                let x = rng.gen_range(0..64);
+                let added = basis.insert(x);
+                assert_eq!(added, !span.contains(&x));
+                if added {
+                    span = span.iter().flat_map(|&y| vec![y, y ^ x]).collect();
+                }
+            }
+            span.sort_unstable();
+            assert_eq!(basis.len(), span.len().trailing_zeros() as usize);
+            for (k, &y) in span.iter().enumerate() {
+                assert_eq!(basis.kth(k as u64), Some(y));
+            }
+            assert_eq!(basis.kth(span.len() as u64), None);
+            for x in 0..64 {
+                assert_eq!(basis.contains(x), span.contains(&x));
+                assert_eq!(basis.max_xor(x), span.iter().map(|&y| x ^ y).max().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_out_of_row() {
+        BitMatrix::new(2, 64).set(0, 64, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_out_of_row() {
+        BitMatrix::new(2, 3).get(0, 5);
+    }
+}
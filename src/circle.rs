@@ -0,0 +1,263 @@
+use crate::complex::{Complex, EPS};
+use crate::line::Line;
+use cargo_snippet::snippet;
+
+#[snippet(name = "Circle", include = "Line")]
+#[derive(Clone, Copy, Debug)]
+pub struct Circle {
+    pub c: Complex,
+    pub r: f64,
+}
+
+#[snippet("Circle")]
+impl Circle {
+    pub fn new(c: Complex, r: f64) -> Self {
+        Circle { c, r }
+    }
+
+    pub fn contains(&self, p: Complex) -> bool {
+        (p - self.c).abs() <= self.r + EPS
+    }
+
+    pub fn intersect_line(&self, l: &Line) -> Vec<Complex> {
+        let p = l.projection(self.c);
+        let h2 = self.r * self.r - (p - self.c).norm();
+        if h2 < -EPS {
+            return vec![];
+        }
+        if h2 < EPS {
+            return vec![p];
+        }
+        let h = l.dir().unit() * h2.sqrt();
+        vec![p - h, p + h]
+    }
+
+    pub fn intersect_circle(&self, other: &Circle) -> Vec<Complex> {
+        let v = other.c - self.c;
+        let d = v.abs();
+        if d < EPS || d > self.r + other.r + EPS || d < (self.r - other.r).abs() - EPS {
+            return vec![];
+        }
+        let a = (self.r * self.r - other.r * other.r + d * d) / (2. * d);
+        let h2 = self.r * self.r - a * a;
+        let p = self.c + v * (a / d);
+        if h2 < EPS {
+            return vec![p];
+        }
+        let h = v.rot90() * (h2.sqrt() / d);
+        vec![p - h, p + h]
+    }
+
+    /// Common tangents as lines from the touching point on `self` to the one on `other`.
+    /// Outer tangents come first, then inner ones.
+    pub fn common_tangents(&self, other: &Circle) -> Vec<Line> {
+        let mut res = vec![];
+        for &r2 in &[other.r, -other.r] {
+            let d = other.c - self.c;
+            let dr = self.r - r2;
+            let d2 = d.norm();
+            let h2 = d2 - dr * dr;
+            if d2 < EPS || h2 < -EPS {
+                continue;
+            }
+            let h = h2.max(0.).sqrt();
+            for &sign in &[-1., 1.] {
+                let v = (d * dr + d.rot90() * h * sign) / d2;
+                res.push(Line::new(self.c + v * self.r, other.c + v * r2));
+                if h < EPS {
+                    break;
+                }
+            }
+        }
+        res
+    }
+
+    pub fn circumcircle(a: Complex, b: Complex, c: Complex) -> Option<Circle> {
+        let (b, c) = (b - a, c - a);
+        let d = b.cross(c);
+        if d.abs() < EPS {
+            return None;
+        }
+        let o = (b * c.norm() - c * b.norm()).rot90() / (2. * d);
+        Some(Circle::new(a + o, o.abs()))
+    }
+
+    pub fn incircle(a: Complex, b: Complex, c: Complex) -> Option<Circle> {
+        let (la, lb, lc) = ((b - c).abs(), (c - a).abs(), (a - b).abs());
+        let s = la + lb + lc;
+        let area = (b - a).cross(c - a).abs();
+        if area < EPS {
+            return None;
+        }
+        Some(Circle::new((a * la + b * lb + c * lc) / s, area / s))
+    }
+
+    pub fn intersection_area(&self, other: &Circle) -> f64 {
+        let d = (other.c - self.c).abs();
+        let (r1, r2) = (self.r, other.r);
+        if d >= r1 + r2 {
+            return 0.;
+        }
+        if d <= (r1 - r2).abs() {
+            let r = r1.min(r2);
+            return std::f64::consts::PI * r * r;
+        }
+        let a1 = ((d * d + r1 * r1 - r2 * r2) / (2. * d * r1))
+            .clamp(-1., 1.)
+            .acos();
+        let a2 = ((d * d + r2 * r2 - r1 * r1) / (2. * d * r2))
+            .clamp(-1., 1.)
+            .acos();
+        r1 * r1 * (a1 - (2. * a1).sin() / 2.) + r2 * r2 * (a2 - (2. * a2).sin() / 2.)
+    }
+}
+
+/// Welzl's algorithm, expected O(n). Panics if `points` is empty.
+#[snippet(name = "min_enclosing_circle", include = "Circle")]
+pub fn min_enclosing_circle(points: &[Complex]) -> Circle {
+    use rand::seq::SliceRandom;
+    assert!(!points.is_empty(), "no points to enclose");
+    let mut ps = points.to_vec();
+    ps.shuffle(&mut rand::thread_rng());
+    let mut res = Circle::new(ps[0], 0.);
+    for i in 1..ps.len() {
+        if res.contains(ps[i]) {
+            continue;
+        }
+        res = Circle::new(ps[i], 0.);
+        for j in 0..i {
+            if res.contains(ps[j]) {
+                continue;
+            }
+            let o = (ps[i] + ps[j]) / 2.;
+            res = Circle::new(o, (o - ps[i]).abs());
+            for k in 0..j {
+                if res.contains(ps[k]) {
+                    continue;
+                }
+                if let Some(c) = Circle::circumcircle(ps[i], ps[j], ps[k]) {
+                    res = c;
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{min_enclosing_circle, Circle};
+    use crate::complex::Complex;
+    use crate::line::Line;
+
+    fn close(a: Complex, x: f64, y: f64) -> bool {
+        (a.x - x).abs() < 1e-6 && (a.y - y).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_intersections() {
+        let c = Circle::new(Complex::new(0., 0.), 1.);
+        let l = Line::new(Complex::new(-2., 0.), Complex::new(2., 0.));
+        let ps = c.intersect_line(&l);
+        assert!(close(ps[0], -1., 0.) && close(ps[1], 1., 0.));
+        let l = Line::new(Complex::new(-2., 1.), Complex::new(2., 1.));
+        assert_eq!(c.intersect_line(&l).len(), 1);
+
+        let d = Circle::new(Complex::new(1., 0.), 1.);
+        let ps = c.intersect_circle(&d);
+        assert_eq!(ps.len(), 2);
+        assert!(close(ps[0], 0.5, -(0.75f64).sqrt()) && close(ps[1], 0.5, (0.75f64).sqrt()));
+        let d = Circle::new(Complex::new(2., 0.), 1.);
+        assert_eq!(c.intersect_circle(&d).len(), 1);
+        let d = Circle::new(Complex::new(3., 0.), 1.);
+        assert!(c.intersect_circle(&d).is_empty());
+    }
+
+    #[test]
+    fn test_common_tangents() {
+        let c = Circle::new(Complex::new(0., 0.), 1.);
+        assert_eq!(
+            c.common_tangents(&Circle::new(Complex::new(4., 0.), 1.))
+                .len(),
+            4
+        );
+        assert_eq!(
+            c.common_tangents(&Circle::new(Complex::new(2., 0.), 1.))
+                .len(),
+            3
+        );
+        assert_eq!(
+            c.common_tangents(&Circle::new(Complex::new(1., 0.), 1.))
+                .len(),
+            2
+        );
+        assert_eq!(
+            c.common_tangents(&Circle::new(Complex::new(0.5, 0.), 0.5))
+                .len(),
+            1
+        );
+        assert!(c
+            .common_tangents(&Circle::new(Complex::new(0., 0.), 0.5))
+            .is_empty());
+
+        let d = Circle::new(Complex::new(4., 1.), 2.);
+        for l in c.common_tangents(&d) {
+            assert!((l.distance(c.c) - c.r).abs() < 1e-6);
+            assert!((l.distance(d.c) - d.r).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_triangle_circles() {
+        let (a, b, c) = (
+            Complex::new(0., 0.),
+            Complex::new(4., 0.),
+            Complex::new(0., 3.),
+        );
+        let cc = Circle::circumcircle(a, b, c).unwrap();
+        assert!(close(cc.c, 2., 1.5) && (cc.r - 2.5).abs() < 1e-9);
+        let ic = Circle::incircle(a, b, c).unwrap();
+        assert!(close(ic.c, 1., 1.) && (ic.r - 1.).abs() < 1e-9);
+        assert!(Circle::circumcircle(a, b, b * 2.).is_none());
+    }
+
+    #[test]
+    fn test_intersection_area() {
+        let pi = std::f64::consts::PI;
+        let c = Circle::new(Complex::new(0., 0.), 1.);
+        assert!(
+            c.intersection_area(&Circle::new(Complex::new(3., 0.), 1.))
+                .abs()
+                < 1e-9
+        );
+        assert!((c.intersection_area(&Circle::new(Complex::new(0.1, 0.), 2.)) - pi).abs() < 1e-9);
+        let lens = 2. * pi / 3. - 3f64.sqrt() / 2.;
+        assert!((c.intersection_area(&Circle::new(Complex::new(1., 0.), 1.)) - lens).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_min_enclosing_circle() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let ps = (0..200)
+                .map(|_| Complex::new(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)))
+                .collect::<Vec<_>>();
+            let c = min_enclosing_circle(&ps);
+            assert!(ps.iter().all(|&p| (p - c.c).abs() <= c.r + 1e-6));
+            let on = ps
+                .iter()
+                .filter(|&&p| ((p - c.c).abs() - c.r).abs() < 1e-6)
+                .count();
+            assert!(on >= 2);
+        }
+        let c = min_enclosing_circle(&[Complex::new(1., 1.)]);
+        assert!(close(c.c, 1., 1.) && c.r == 0.);
+    }
+
+    #[test]
+    #[should_panic(expected = "no points to enclose")]
+    fn test_min_enclosing_circle_empty() {
+        min_enclosing_circle(&[]);
+    }
+}
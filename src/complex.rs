@@ -2,6 +2,9 @@ use cargo_snippet::snippet;
 #[snippet("Complex")]
 use std::ops::*;
 
+#[snippet("Complex")]
+pub const EPS: f64 = 1e-9;
+
 #[snippet("Complex")]
 #[derive(Clone, Copy, Debug)]
 pub struct Complex {
@@ -16,7 +19,27 @@ impl Complex {
     }
 
     pub fn abs(&self) -> f64 {
-        (self.x * self.x + self.y + self.y).sqrt()
+        self.norm().sqrt()
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.x * self.x + self.y * self.y
+    }
+
+    pub fn dot(&self, rhs: Self) -> f64 {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    pub fn cross(&self, rhs: Self) -> f64 {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    pub fn unit(&self) -> Self {
+        *self / self.abs()
+    }
+
+    pub fn rot90(&self) -> Self {
+        Complex::new(-self.y, self.x)
     }
 
     pub fn arg(&self) -> f64 {
@@ -73,12 +96,36 @@ impl Mul for Complex {
 impl Div for Complex {
     type Output = Self;
     fn div(self, rhs: Self) -> Self {
-        let a = self * self.con();
-        let b = rhs.x * rhs.x + rhs.y + rhs.y;
+        let a = self * rhs.con();
+        let b = rhs.norm();
         Complex::new(a.x / b, a.y / b)
     }
 }
 
+#[snippet("Complex")]
+impl Mul<f64> for Complex {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Complex::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[snippet("Complex")]
+impl Div<f64> for Complex {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Complex::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+#[snippet("Complex")]
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Complex::new(-self.x, -self.y)
+    }
+}
+
 #[snippet("Complex")]
 impl AddAssign for Complex {
     fn add_assign(&mut self, rhs: Self) {
@@ -123,4 +170,12 @@ mod tests {
         let c = Complex::new(1., 0.);
         assert!(c.angle() - 0. < 1e-10);
     }
+
+    #[test]
+    fn test_abs_div() {
+        let c = Complex::new(3., 4.);
+        assert!((c.abs() - 5.).abs() < 1e-10);
+        let d = Complex::new(1., 2.) * c / c;
+        assert!((d.x - 1.).abs() < 1e-10 && (d.y - 2.).abs() < 1e-10);
+    }
 }
@@ -0,0 +1,397 @@
+use crate::circle::Circle;
+use crate::complex::Complex;
+use crate::point::Point;
+use crate::union_find::UnionFind;
+use cargo_snippet::snippet;
+
+/// Quad-edge structure. Quads `4k..4k + 4` are the rotations of the `k`-th edge
+/// and `q ^ 2` is the reverse of `q`.
+#[snippet(name = "Delaunay", include = "Point, Circle")]
+struct QuadEdge<'a> {
+    ps: &'a [Point],
+    o: Vec<usize>,
+    p: Vec<usize>,
+    mark: Vec<bool>,
+}
+
+#[snippet("Delaunay")]
+impl<'a> QuadEdge<'a> {
+    const ARB: usize = usize::MAX;
+
+    fn rot(q: usize) -> usize {
+        (q & !3) | ((q + 1) & 3)
+    }
+
+    fn sym(q: usize) -> usize {
+        q ^ 2
+    }
+
+    fn dest(&self, q: usize) -> usize {
+        self.p[Self::sym(q)]
+    }
+
+    fn prev(&self, q: usize) -> usize {
+        Self::rot(self.o[Self::rot(q)])
+    }
+
+    fn next(&self, q: usize) -> usize {
+        self.prev(Self::sym(q))
+    }
+
+    fn cross(&self, p: usize, a: usize, b: usize) -> i64 {
+        (self.ps[a] - self.ps[p]).cross(self.ps[b] - self.ps[p])
+    }
+
+    /// Whether `p` is strictly inside the circumcircle of the counter-clockwise `a, b, c`.
+    fn in_circle(&self, p: usize, a: usize, b: usize, c: usize) -> bool {
+        let p = self.ps[p];
+        let (a, b, c) = (self.ps[a] - p, self.ps[b] - p, self.ps[c] - p);
+        let (na, nb, nc) = (a.norm() as i128, b.norm() as i128, c.norm() as i128);
+        a.cross(b) as i128 * nc + b.cross(c) as i128 * na + c.cross(a) as i128 * nb > 0
+    }
+
+    fn make_edge(&mut self, orig: usize, dest: usize) -> usize {
+        let q = self.o.len();
+        self.o.extend_from_slice(&[q, q + 3, q + 2, q + 1]);
+        self.p
+            .extend_from_slice(&[orig, Self::ARB, dest, Self::ARB]);
+        self.mark.extend_from_slice(&[false; 4]);
+        q
+    }
+
+    fn splice(&mut self, a: usize, b: usize) {
+        let (x, y) = (Self::rot(self.o[a]), Self::rot(self.o[b]));
+        self.o.swap(x, y);
+        self.o.swap(a, b);
+    }
+
+    fn connect(&mut self, a: usize, b: usize) -> usize {
+        let q = self.make_edge(self.dest(a), self.p[b]);
+        self.splice(q, self.next(a));
+        self.splice(Self::sym(q), b);
+        q
+    }
+
+    fn delete(&mut self, e: usize) {
+        self.splice(e, self.prev(e));
+        let r = Self::sym(e);
+        self.splice(r, self.prev(r));
+    }
+
+    fn rec(&mut self, s: &[usize]) -> (usize, usize) {
+        let n = s.len();
+        if n <= 3 {
+            let a = self.make_edge(s[0], s[1]);
+            if n == 2 {
+                return (a, Self::sym(a));
+            }
+            let b = self.make_edge(s[1], s[2]);
+            self.splice(Self::sym(a), b);
+            let side = self.cross(s[0], s[1], s[2]);
+            if side == 0 {
+                return (a, Self::sym(b));
+            }
+            let c = self.connect(b, a);
+            return if side < 0 {
+                (Self::sym(c), c)
+            } else {
+                (a, Self::sym(b))
+            };
+        }
+
+        let (mut ra, mut a) = self.rec(&s[..n - n / 2]);
+        let (mut b, mut rb) = self.rec(&s[n - n / 2..]);
+        loop {
+            if self.cross(self.p[b], self.dest(a), self.p[a]) < 0 {
+                a = self.next(a);
+            } else if self.cross(self.p[a], self.dest(b), self.p[b]) > 0 {
+                b = self.o[Self::sym(b)];
+            } else {
+                break;
+            }
+        }
+        let mut base = self.connect(Self::sym(b), a);
+        if self.p[a] == self.p[ra] {
+            ra = Self::sym(base);
+        }
+        if self.p[b] == self.p[rb] {
+            rb = base;
+        }
+
+        loop {
+            let valid = |qe: &Self, e: usize, base: usize| {
+                qe.cross(qe.dest(e), qe.dest(base), qe.p[base]) > 0
+            };
+
+            let mut lc = self.o[Self::sym(base)];
+            if valid(self, lc, base) {
+                while self.in_circle(
+                    self.dest(self.o[lc]),
+                    self.dest(base),
+                    self.p[base],
+                    self.dest(lc),
+                ) {
+                    let t = self.o[lc];
+                    self.delete(lc);
+                    lc = t;
+                }
+            }
+            let mut rc = self.prev(base);
+            if valid(self, rc, base) {
+                while self.in_circle(
+                    self.dest(self.prev(rc)),
+                    self.dest(base),
+                    self.p[base],
+                    self.dest(rc),
+                ) {
+                    let t = self.prev(rc);
+                    self.delete(rc);
+                    rc = t;
+                }
+            }
+
+            let (vl, vr) = (valid(self, lc, base), valid(self, rc, base));
+            if !vl && !vr {
+                break;
+            }
+            let right = vr && self.in_circle(self.dest(rc), self.p[rc], self.dest(lc), self.p[lc]);
+            if !vl || right {
+                base = self.connect(rc, Self::sym(base));
+            } else {
+                base = self.connect(Self::sym(base), Self::sym(lc));
+            }
+        }
+        (ra, rb)
+    }
+}
+
+/// Delaunay triangulation in O(n log n) by divide and conquer with exact predicates.
+/// Points must be distinct with coordinates up to 1e9 in absolute value.
+#[snippet("Delaunay")]
+#[derive(Clone, Debug)]
+pub struct Delaunay {
+    /// Triangles as indices of the input points, in counter-clockwise order.
+    pub triangles: Vec<[usize; 3]>,
+    /// Edges `(u, v)` with `u < v`. Non-empty even when all points are collinear.
+    pub edges: Vec<(usize, usize)>,
+}
+
+#[snippet("Delaunay")]
+#[derive(Clone, Copy, Debug)]
+pub enum VoronoiEdge {
+    Segment(Complex, Complex),
+    /// Starts at the first point and extends in the direction of the second.
+    Ray(Complex, Complex),
+    /// Passes through the first point in the direction of the second.
+    Line(Complex, Complex),
+}
+
+#[snippet("Delaunay")]
+impl Delaunay {
+    pub fn new(ps: &[Point]) -> Self {
+        let mut res = Delaunay {
+            triangles: vec![],
+            edges: vec![],
+        };
+        if ps.len() < 2 {
+            return res;
+        }
+        let mut s = (0..ps.len()).collect::<Vec<_>>();
+        s.sort_by_key(|&i| (ps[i].x, ps[i].y));
+        assert!(s.windows(2).all(|w| ps[w[0]] != ps[w[1]]));
+
+        let mut qe = QuadEdge {
+            ps,
+            o: vec![],
+            p: vec![],
+            mark: vec![],
+        };
+        let mut e = qe.rec(&s).0;
+        while qe.cross(qe.dest(qe.o[e]), qe.dest(e), qe.p[e]) < 0 {
+            e = qe.o[e];
+        }
+
+        let mut queue = vec![e];
+        let mut face = vec![];
+        let mut visit =
+            |qe: &mut QuadEdge, e: usize, face: &mut Vec<usize>, queue: &mut Vec<usize>| {
+                let mut c = e;
+                loop {
+                    qe.mark[c] = true;
+                    face.push(qe.p[c]);
+                    if qe.p[c] < qe.dest(c) {
+                        res.edges.push((qe.p[c], qe.dest(c)));
+                    }
+                    queue.push(QuadEdge::sym(c));
+                    c = qe.next(c);
+                    if c == e {
+                        break;
+                    }
+                }
+            };
+        visit(&mut qe, e, &mut face, &mut queue);
+        face.clear();
+        let mut qi = 0;
+        while qi < queue.len() {
+            let e = queue[qi];
+            qi += 1;
+            if !qe.mark[e] {
+                visit(&mut qe, e, &mut face, &mut queue);
+            }
+        }
+        res.triangles = face.chunks(3).map(|t| [t[0], t[1], t[2]]).collect();
+        res
+    }
+
+    /// Voronoi edges `(u, v, edge)` separating the cells of `ps[u]` and `ps[v]`.
+    pub fn voronoi(&self, ps: &[Point]) -> Vec<(usize, usize, VoronoiEdge)> {
+        use std::collections::HashMap;
+        let to_c = |p: Point| Complex::new(p.x as f64, p.y as f64);
+        let mut left = HashMap::new();
+        for t in &self.triangles {
+            let c = Circle::circumcircle(to_c(ps[t[0]]), to_c(ps[t[1]]), to_c(ps[t[2]]))
+                .unwrap()
+                .c;
+            for i in 0..3 {
+                left.insert((t[i], t[(i + 1) % 3]), c);
+            }
+        }
+        self.edges
+            .iter()
+            .map(|&(u, v)| {
+                let d = to_c(ps[v] - ps[u]);
+                let edge = match (left.get(&(u, v)), left.get(&(v, u))) {
+                    (Some(&a), Some(&b)) => VoronoiEdge::Segment(a, b),
+                    (Some(&a), None) => VoronoiEdge::Ray(a, -d.rot90()),
+                    (None, Some(&b)) => VoronoiEdge::Ray(b, d.rot90()),
+                    (None, None) => VoronoiEdge::Line((to_c(ps[u]) + to_c(ps[v])) / 2., d.rot90()),
+                };
+                (u, v, edge)
+            })
+            .collect()
+    }
+}
+
+/// Euclidean minimum spanning tree in O(n log n) using the Delaunay edges.
+#[snippet(name = "euclidean_mst", include = "Delaunay, UnionFind")]
+pub fn euclidean_mst(ps: &[Point]) -> Vec<(usize, usize)> {
+    let mut edges = Delaunay::new(ps).edges;
+    edges.sort_by_key(|&(u, v)| (ps[u] - ps[v]).norm());
+    let mut uf = UnionFind::new(ps.len());
+    edges.into_iter().filter(|&(u, v)| uf.unite(u, v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{euclidean_mst, Delaunay, VoronoiEdge};
+    use crate::point::Point;
+
+    fn random_points(n: usize, m: i64) -> Vec<Point> {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        let mut ps = (0..n)
+            .map(|_| Point::new(rng.gen_range(-m..m), rng.gen_range(-m..m)))
+            .collect::<Vec<_>>();
+        ps.sort_by_key(|p| (p.x, p.y));
+        ps.dedup();
+        ps.shuffle(&mut rng);
+        ps
+    }
+
+    #[test]
+    fn test_delaunay_square() {
+        let ps = vec![
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 2),
+            Point::new(0, 2),
+            Point::new(1, 3),
+        ];
+        let d = Delaunay::new(&ps);
+        assert_eq!(d.triangles.len(), 3);
+        assert_eq!(d.edges.len(), 7);
+
+        let d = Delaunay::new(&[Point::new(0, 0), Point::new(1, 1), Point::new(3, 3)]);
+        assert!(d.triangles.is_empty());
+        let mut edges = d.edges.clone();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(0, 1), (1, 2)]);
+        assert!(d
+            .voronoi(&[Point::new(0, 0), Point::new(1, 1), Point::new(3, 3)])
+            .iter()
+            .all(|(_, _, e)| matches!(e, VoronoiEdge::Line(..))));
+    }
+
+    #[test]
+    fn test_delaunay_random() {
+        for _ in 0..30 {
+            let ps = random_points(60, 30);
+            let d = Delaunay::new(&ps);
+            for t in &d.triangles {
+                let (a, b, c) = (ps[t[0]], ps[t[1]], ps[t[2]]);
+                assert!((b - a).cross(c - a) > 0);
+                for &p in &ps {
+                    let (a, b, c) = (a - p, b - p, c - p);
+                    let det = a.cross(b) * c.norm() + b.cross(c) * a.norm() + c.cross(a) * b.norm();
+                    assert!(det <= 0);
+                }
+            }
+            let area: i64 = d
+                .triangles
+                .iter()
+                .map(|t| (ps[t[1]] - ps[t[0]]).cross(ps[t[2]] - ps[t[0]]))
+                .sum();
+            let mut sorted = ps.clone();
+            sorted.sort_by_key(|p| (p.x, p.y));
+            let mut hull: Vec<Point> = vec![];
+            for pass in 0..2 {
+                let t = hull.len();
+                for &p in sorted.iter() {
+                    while hull.len() >= t + 2 && {
+                        let k = hull.len();
+                        (hull[k - 1] - hull[k - 2]).cross(p - hull[k - 2]) <= 0
+                    } {
+                        hull.pop();
+                    }
+                    hull.push(p);
+                }
+                if pass == 0 {
+                    hull.pop();
+                    sorted.reverse();
+                }
+            }
+            let hull_area: i64 = hull.windows(2).map(|w| w[0].cross(w[1])).sum();
+            assert_eq!(area, hull_area);
+            assert_eq!(d.voronoi(&ps).len(), d.edges.len());
+        }
+    }
+
+    #[test]
+    fn test_euclidean_mst() {
+        for _ in 0..30 {
+            let ps = random_points(80, 100);
+            let n = ps.len();
+            let weight = |es: &[(usize, usize)]| {
+                es.iter()
+                    .map(|&(u, v)| ((ps[u] - ps[v]).norm() as f64).sqrt())
+                    .sum::<f64>()
+            };
+            let mst = euclidean_mst(&ps);
+            assert_eq!(mst.len(), n - 1);
+
+            let mut all = vec![];
+            for i in 0..n {
+                for j in i + 1..n {
+                    all.push((i, j));
+                }
+            }
+            all.sort_by_key(|&(u, v)| (ps[u] - ps[v]).norm());
+            let mut uf = crate::union_find::UnionFind::new(n);
+            let naive = all
+                .into_iter()
+                .filter(|&(u, v)| uf.unite(u, v))
+                .collect::<Vec<_>>();
+            assert!((weight(&mst) - weight(&naive)).abs() < 1e-6);
+        }
+    }
+}
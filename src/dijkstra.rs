@@ -14,7 +14,7 @@ impl Edge {
 
 pub fn dijkstra(graph: &[Vec<Edge>], start: usize) -> Vec<i64> {
     use std::collections::BinaryHeap;
-    let mut dist: Vec<_> = (0..graph.len()).map(|_| std::i64::MAX).collect();
+    let mut dist: Vec<_> = (0..graph.len()).map(|_| i64::MAX).collect();
     let mut heap = BinaryHeap::new();
     dist[start] = 0;
     heap.push(State {
@@ -77,7 +77,7 @@ mod tests {
         }
 
         let dist = dijkstra(&graph, 0);
-        let acc = vec![0, 3, 1, 3, 4];
+        let acc = [0, 3, 1, 3, 4];
         for i in 0..5 {
             assert_eq!(dist[i], acc[i]);
         }
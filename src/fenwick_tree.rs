@@ -0,0 +1,461 @@
+use crate::number::Number;
+use crate::util::to_range;
+use cargo_snippet::snippet;
+
+#[snippet(name = "FenwickTree", include = "to_range")]
+pub struct FenwickTree<T> {
+    tree: Vec<T>,
+    len: usize,
+}
+
+#[snippet("FenwickTree")]
+impl<T> From<Vec<T>> for FenwickTree<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    fn from(s: Vec<T>) -> Self {
+        let len = s.len();
+        let mut tree = vec![T::zero(); len + 1];
+        tree[1..].copy_from_slice(&s);
+        for i in 1..=len {
+            let j = i + (i & i.wrapping_neg());
+            if j <= len {
+                tree[j] = tree[j] + tree[i];
+            }
+        }
+        Self { tree, len }
+    }
+}
+
+#[snippet("FenwickTree")]
+impl<T> FenwickTree<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(len: usize) -> Self {
+        Self {
+            tree: vec![T::zero(); len + 1],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn add(&mut self, i: usize, element: T) {
+        assert!(i < self.len);
+        let mut i = i + 1;
+        while i <= self.len {
+            self.tree[i] = self.tree[i] + element;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum of `[0, r)`.
+    fn prefix(&self, mut r: usize) -> T {
+        let mut res = T::zero();
+        while r > 0 {
+            res = res + self.tree[r];
+            r &= r - 1;
+        }
+        res
+    }
+
+    pub fn sum(&self, range: impl std::ops::RangeBounds<usize>) -> T {
+        let (l, r) = to_range(range, self.len);
+        self.prefix(r) - self.prefix(l)
+    }
+
+    /// Smallest `i` with `sum(..=i) >= w`, or `len` if there is none.
+    /// All elements must be non-negative. With counts as elements,
+    /// `lower_bound(k + 1)` is the `k`-th (0-indexed) smallest element.
+    pub fn lower_bound(&self, mut w: T) -> usize
+    where
+        T: PartialOrd,
+    {
+        if w <= T::zero() {
+            return 0;
+        }
+        let mut i = 0;
+        let mut step = (self.len + 1).next_power_of_two() >> 1;
+        while step > 0 {
+            if i + step <= self.len && self.tree[i + step] < w {
+                i += step;
+                w = w - self.tree[i];
+            }
+            step >>= 1;
+        }
+        i
+    }
+}
+
+#[snippet(name = "FenwickTree2D", include = "FenwickTree")]
+pub struct FenwickTree2D<T> {
+    tree: Vec<Vec<T>>,
+    h: usize,
+    w: usize,
+}
+
+#[snippet("FenwickTree2D")]
+impl<T> FenwickTree2D<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            tree: vec![vec![T::zero(); w + 1]; h + 1],
+            h,
+            w,
+        }
+    }
+
+    pub fn add(&mut self, x: usize, y: usize, element: T) {
+        assert!(x < self.w && y < self.h);
+        let mut i = y + 1;
+        while i <= self.h {
+            let mut j = x + 1;
+            while j <= self.w {
+                self.tree[i][j] = self.tree[i][j] + element;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum of `[0, x) x [0, y)`.
+    fn prefix(&self, x: usize, mut y: usize) -> T {
+        let mut res = T::zero();
+        while y > 0 {
+            let mut j = x;
+            while j > 0 {
+                res = res + self.tree[y][j];
+                j &= j - 1;
+            }
+            y &= y - 1;
+        }
+        res
+    }
+
+    pub fn sum(
+        &self,
+        x: impl std::ops::RangeBounds<usize>,
+        y: impl std::ops::RangeBounds<usize>,
+    ) -> T {
+        let (x1, x2) = to_range(x, self.w);
+        let (y1, y2) = to_range(y, self.h);
+        self.prefix(x2, y2) - self.prefix(x1, y2) - self.prefix(x2, y1) + self.prefix(x1, y1)
+    }
+}
+
+/// 2D Fenwick tree over sparse points known in advance, using O(n log n) memory.
+#[snippet("OfflineFenwickTree2D")]
+pub struct OfflineFenwickTree2D<T> {
+    xs: Vec<i64>,
+    ys: Vec<Vec<i64>>,
+    tree: Vec<Vec<T>>,
+}
+
+#[snippet("OfflineFenwickTree2D")]
+impl<T> OfflineFenwickTree2D<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    /// `points` are all the points that will be passed to `add`.
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let mut xs = points.iter().map(|p| p.0).collect::<Vec<_>>();
+        xs.sort_unstable();
+        xs.dedup();
+        let mut ps = points.to_vec();
+        ps.sort_unstable_by_key(|p| p.1);
+        let mut ys = vec![vec![]; xs.len() + 1];
+        for (x, y) in ps {
+            let mut i = xs.binary_search(&x).unwrap() + 1;
+            while i <= xs.len() {
+                if ys[i].last() != Some(&y) {
+                    ys[i].push(y);
+                }
+                i += i & i.wrapping_neg();
+            }
+        }
+        let tree = ys.iter().map(|y| vec![T::zero(); y.len() + 1]).collect();
+        Self { xs, ys, tree }
+    }
+
+    /// Panics if `(x, y)` was not given to `new`.
+    pub fn add(&mut self, x: i64, y: i64, element: T) {
+        let mut i = self.xs.binary_search(&x).expect("unknown point") + 1;
+        while i <= self.xs.len() {
+            let mut j = self.ys[i].binary_search(&y).expect("unknown point") + 1;
+            while j <= self.ys[i].len() {
+                self.tree[i][j] = self.tree[i][j] + element;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum over points with `px < x` and `py < y`.
+    fn prefix(&self, x: i64, y: i64) -> T {
+        let mut res = T::zero();
+        let mut i = self.xs.partition_point(|&px| px < x);
+        while i > 0 {
+            let mut j = self.ys[i].partition_point(|&py| py < y);
+            while j > 0 {
+                res = res + self.tree[i][j];
+                j &= j - 1;
+            }
+            i &= i - 1;
+        }
+        res
+    }
+
+    /// Sum over points in `[x1, x2) x [y1, y2)`.
+    pub fn sum(&self, x1: i64, x2: i64, y1: i64, y2: i64) -> T {
+        self.prefix(x2, y2) - self.prefix(x1, y2) - self.prefix(x2, y1) + self.prefix(x1, y1)
+    }
+}
+
+/// Fenwick tree supporting range add and range sum, built from two `FenwickTree`s.
+#[snippet(name = "RangeFenwickTree", include = "FenwickTree")]
+pub struct RangeFenwickTree<T> {
+    // sum of `[0, i)` is `i * d.sum(..i) - id.sum(..i)` for the differences `d`
+    d: FenwickTree<T>,
+    id: FenwickTree<T>,
+    len: usize,
+}
+
+#[snippet("RangeFenwickTree")]
+impl<T> From<Vec<T>> for RangeFenwickTree<T>
+where
+    T: Number
+        + Clone
+        + Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>,
+{
+    fn from(s: Vec<T>) -> Self {
+        let len = s.len();
+        let mut d = vec![T::zero(); len + 1];
+        let mut id = vec![T::zero(); len + 1];
+        let mut i = T::zero();
+        for k in 0..len {
+            d[k] = if k == 0 { s[0] } else { s[k] - s[k - 1] };
+            id[k] = d[k] * i;
+            i = i + T::one();
+        }
+        Self {
+            d: FenwickTree::from(d),
+            id: FenwickTree::from(id),
+            len,
+        }
+    }
+}
+
+#[snippet("RangeFenwickTree")]
+impl<T> RangeFenwickTree<T>
+where
+    T: Number
+        + Clone
+        + Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>,
+{
+    pub fn new(len: usize) -> Self {
+        Self {
+            d: FenwickTree::new(len + 1),
+            id: FenwickTree::new(len + 1),
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// `i` as `T`, by doubling in O(log i).
+    fn cast(i: usize) -> T {
+        let mut res = T::zero();
+        for b in (0..usize::BITS - i.leading_zeros()).rev() {
+            res = res + res;
+            if i >> b & 1 == 1 {
+                res = res + T::one();
+            }
+        }
+        res
+    }
+
+    fn point_add(&mut self, i: usize, element: T) {
+        self.d.add(i, element);
+        self.id.add(i, element * Self::cast(i));
+    }
+
+    fn prefix(&self, i: usize) -> T {
+        Self::cast(i) * self.d.sum(..i) - self.id.sum(..i)
+    }
+
+    pub fn range_add(&mut self, range: impl std::ops::RangeBounds<usize>, element: T) {
+        let (l, r) = to_range(range, self.len);
+        self.point_add(l, element);
+        self.point_add(r, T::zero() - element);
+    }
+
+    pub fn range_sum(&self, range: impl std::ops::RangeBounds<usize>) -> T {
+        let (l, r) = to_range(range, self.len);
+        self.prefix(r) - self.prefix(l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FenwickTree, FenwickTree2D, OfflineFenwickTree2D, RangeFenwickTree};
+    use rand::prelude::*;
+
+    #[test]
+    fn test_fenwick_tree() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0..50);
+            let mut vec = (0..n).map(|_| rng.gen_range(0..10)).collect::<Vec<i64>>();
+            let mut ft = FenwickTree::from(vec.clone());
+            assert_eq!(ft.len(), n);
+            for _ in 0..50 {
+                if n > 0 {
+                    let i = rng.gen_range(0..n);
+                    let x = rng.gen_range(0..10);
+                    vec[i] += x;
+                    ft.add(i, x);
+                }
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                assert_eq!(ft.sum(l..r), vec[l..r].iter().sum());
+                assert_eq!(ft.sum(..r), vec[..r].iter().sum());
+                assert_eq!(ft.sum(l..), vec[l..].iter().sum());
+                if r > l {
+                    assert_eq!(ft.sum(l..=r - 1), vec[l..r].iter().sum());
+                }
+                assert_eq!(ft.sum(..), vec.iter().sum());
+
+                let w = rng.gen_range(0..=vec.iter().sum::<i64>() + 1);
+                let expected = (0..n)
+                    .find(|&i| vec[..=i].iter().sum::<i64>() >= w)
+                    .unwrap_or(n);
+                assert_eq!(ft.lower_bound(w), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kth_element() {
+        let mut cnt = FenwickTree::new(10);
+        for &x in &[3, 1, 4, 1, 5, 9, 2, 6] {
+            cnt.add(x, 1);
+        }
+        let sorted = (0..8).map(|k| cnt.lower_bound(k + 1)).collect::<Vec<_>>();
+        assert_eq!(sorted, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+        assert_eq!(cnt.lower_bound(9), 10);
+    }
+
+    #[test]
+    fn test_fenwick_tree_2d() {
+        let mut rng = thread_rng();
+        let (h, w) = (7, 9);
+        let mut vec = vec![vec![0i64; w]; h];
+        let mut ft = FenwickTree2D::new(h, w);
+        for _ in 0..200 {
+            let (x, y, e) = (
+                rng.gen_range(0..w),
+                rng.gen_range(0..h),
+                rng.gen_range(-10..10),
+            );
+            vec[y][x] += e;
+            ft.add(x, y, e);
+
+            let x1 = rng.gen_range(0..=w);
+            let x2 = rng.gen_range(x1..=w);
+            let y1 = rng.gen_range(0..=h);
+            let y2 = rng.gen_range(y1..=h);
+            let expected = vec[y1..y2]
+                .iter()
+                .map(|r| r[x1..x2].iter().sum::<i64>())
+                .sum();
+            assert_eq!(ft.sum(x1..x2, y1..y2), expected);
+        }
+        let total = vec.iter().flatten().sum();
+        assert_eq!(ft.sum(.., ..), total);
+    }
+
+    #[test]
+    fn test_offline_fenwick_tree_2d() {
+        let mut rng = thread_rng();
+        let points = (0..100)
+            .map(|_| {
+                (
+                    rng.gen_range(-1_000_000_000..1_000_000_000),
+                    rng.gen_range(-20..20),
+                )
+            })
+            .collect::<Vec<(i64, i64)>>();
+        let mut ft = OfflineFenwickTree2D::new(&points);
+        let mut weights = vec![0i64; points.len()];
+        for _ in 0..300 {
+            let k = rng.gen_range(0..points.len());
+            let e = rng.gen_range(-10..10);
+            weights[k] += e;
+            ft.add(points[k].0, points[k].1, e);
+
+            let mut x = [points[rng.gen_range(0..points.len())].0, rng.gen()];
+            let mut y = [rng.gen_range(-25..25), rng.gen_range(-25..25)];
+            x.sort_unstable();
+            y.sort_unstable();
+            let expected = points
+                .iter()
+                .zip(&weights)
+                .filter(|((px, py), _)| x[0] <= *px && *px < x[1] && y[0] <= *py && *py < y[1])
+                .map(|(_, w)| w)
+                .sum();
+            assert_eq!(ft.sum(x[0], x[1], y[0], y[1]), expected);
+        }
+    }
+
+    #[test]
+    fn test_range_fenwick_tree() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0..30);
+            let mut vec = (0..n).map(|_| rng.gen_range(-10..10)).collect::<Vec<i64>>();
+            let mut ft = if rng.gen() {
+                RangeFenwickTree::from(vec.clone())
+            } else {
+                vec = vec![0; n];
+                RangeFenwickTree::new(n)
+            };
+            for _ in 0..30 {
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                let x = rng.gen_range(-10..10);
+                ft.range_add(l..r, x);
+                vec[l..r].iter_mut().for_each(|v| *v += x);
+
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                assert_eq!(ft.range_sum(l..r), vec[l..r].iter().sum());
+                assert_eq!(ft.range_sum(..), vec.iter().sum());
+            }
+        }
+
+        let mut ft = RangeFenwickTree::from(vec![0.5, 1.5]);
+        ft.range_add(.., 1.);
+        assert_eq!(ft.range_sum(1..=1), 2.5);
+    }
+}
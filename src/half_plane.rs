@@ -0,0 +1,181 @@
+use crate::complex::{Complex, EPS};
+use crate::line::Line;
+use cargo_snippet::snippet;
+
+#[snippet(name = "half_plane_intersection", include = "Line")]
+#[derive(Clone, Debug)]
+pub enum HalfPlaneIntersection {
+    Empty,
+    Unbounded,
+    /// Vertices of the convex polygon in counter-clockwise order.
+    Bounded(Vec<Complex>),
+}
+
+/// Intersects the left sides of `lines` in O(n log n).
+/// Regions reaching outside of `[-1e9, 1e9]^2` are reported as unbounded,
+/// and regions with zero area are reported as empty.
+#[snippet("half_plane_intersection")]
+pub fn half_plane_intersection(lines: &[Line]) -> HalfPlaneIntersection {
+    const BOUND: f64 = 1e9;
+    let corners = [
+        Complex::new(-BOUND, -BOUND),
+        Complex::new(BOUND, -BOUND),
+        Complex::new(BOUND, BOUND),
+        Complex::new(-BOUND, BOUND),
+    ];
+    let mut ls = (0..4)
+        .map(|i| (Line::new(corners[i], corners[(i + 1) % 4]), true))
+        .chain(lines.iter().map(|&l| (l, false)))
+        .map(|(l, is_bound)| (l.dir().arg(), l, is_bound))
+        .collect::<Vec<_>>();
+    ls.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let mut uniq: Vec<(f64, Line, bool)> = vec![];
+    for l in ls {
+        match uniq.last_mut() {
+            Some(last) if (last.0 - l.0).abs() < EPS => {
+                if last.1.side(l.1.a) > 0. {
+                    *last = l;
+                }
+            }
+            _ => uniq.push(l),
+        }
+    }
+
+    let mut dq = std::collections::VecDeque::<(Line, bool)>::new();
+    let outside = |l: &Line, m: &Line, n: &Line| match m.intersection(n) {
+        Some(p) => l.side(p) < EPS,
+        None => true,
+    };
+    for (_, l, is_bound) in uniq {
+        while dq.len() >= 2 && outside(&l, &dq[dq.len() - 2].0, &dq[dq.len() - 1].0) {
+            dq.pop_back();
+        }
+        while dq.len() >= 2 && outside(&l, &dq[0].0, &dq[1].0) {
+            dq.pop_front();
+        }
+        dq.push_back((l, is_bound));
+    }
+    while dq.len() >= 3 && outside(&dq[0].0, &dq[dq.len() - 2].0, &dq[dq.len() - 1].0) {
+        dq.pop_back();
+    }
+    while dq.len() >= 3 && outside(&dq[dq.len() - 1].0, &dq[0].0, &dq[1].0) {
+        dq.pop_front();
+    }
+    if dq.len() < 3 {
+        return HalfPlaneIntersection::Empty;
+    }
+
+    let mut res = vec![];
+    for i in 0..dq.len() {
+        match dq[i].0.intersection(&dq[(i + 1) % dq.len()].0) {
+            Some(p) => res.push(p),
+            None => return HalfPlaneIntersection::Empty,
+        }
+    }
+    if dq.iter().any(|&(_, is_bound)| is_bound) {
+        HalfPlaneIntersection::Unbounded
+    } else {
+        HalfPlaneIntersection::Bounded(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{half_plane_intersection, HalfPlaneIntersection};
+    use crate::complex::Complex;
+    use crate::line::Line;
+
+    fn line(ax: f64, ay: f64, bx: f64, by: f64) -> Line {
+        Line::new(Complex::new(ax, ay), Complex::new(bx, by))
+    }
+
+    fn area(ps: &[Complex]) -> f64 {
+        (0..ps.len())
+            .map(|i| ps[i].cross(ps[(i + 1) % ps.len()]))
+            .sum::<f64>()
+            / 2.
+    }
+
+    #[test]
+    fn test_bounded() {
+        let ls = vec![
+            line(0., 0., 1., 0.),
+            line(2., 0., 2., 1.),
+            line(2., 2., 1., 2.),
+            line(0., 2., 0., 1.),
+            line(0., 1., 1., 0.),
+            line(5., 0., 5., 1.),
+            line(3., 0., 4., 0.),
+        ];
+        match half_plane_intersection(&ls) {
+            HalfPlaneIntersection::Bounded(ps) => {
+                assert_eq!(ps.len(), 5);
+                assert!((area(&ps) - 3.5).abs() < 1e-9);
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_empty_and_unbounded() {
+        let ls = vec![line(0., 0., 1., 0.), line(1., -1., 0., -1.)];
+        assert!(matches!(
+            half_plane_intersection(&ls),
+            HalfPlaneIntersection::Empty
+        ));
+        let ls = vec![line(0., 0., 1., 0.), line(1., 1., 0., 1.)];
+        assert!(matches!(
+            half_plane_intersection(&ls),
+            HalfPlaneIntersection::Unbounded
+        ));
+        let ls = vec![
+            line(0., 0., 1., 0.),
+            line(0., 0., 0., -1.),
+            line(0., -1., -1., 0.),
+        ];
+        assert!(matches!(
+            half_plane_intersection(&ls),
+            HalfPlaneIntersection::Empty
+        ));
+        let ls = vec![line(0., 0., 1., 0.), line(0., 0., 1., 1.)];
+        assert!(matches!(
+            half_plane_intersection(&ls),
+            HalfPlaneIntersection::Unbounded
+        ));
+        assert!(matches!(
+            half_plane_intersection(&[]),
+            HalfPlaneIntersection::Unbounded
+        ));
+    }
+
+    #[test]
+    fn test_random() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let ls = (0..rng.gen_range(3..50))
+                .map(|_| {
+                    let t: f64 = rng.gen_range(0.0..std::f64::consts::PI * 2.);
+                    let p = Complex::polar(rng.gen_range(1.0..10.0), t);
+                    Line::new(p, p + p.rot90())
+                })
+                .collect::<Vec<_>>();
+            match half_plane_intersection(&ls) {
+                HalfPlaneIntersection::Bounded(ps) => {
+                    assert!(area(&ps) > 0.);
+                    for p in ps {
+                        assert!(ls.iter().all(|l| l.side(p) > -1e-6));
+                    }
+                }
+                HalfPlaneIntersection::Unbounded => {
+                    let mut ts = ls.iter().map(|l| l.dir().arg()).collect::<Vec<_>>();
+                    ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    ts.push(ts[0] + std::f64::consts::PI * 2.);
+                    assert!(ts.windows(2).any(|w| w[1] - w[0] >= std::f64::consts::PI));
+                }
+                HalfPlaneIntersection::Empty => panic!("contains the origin"),
+            }
+        }
+    }
+}
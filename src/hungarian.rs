@@ -0,0 +1,132 @@
+use crate::matrix::Matrix;
+
+#[derive(Clone, Debug)]
+pub struct Assignment {
+    pub cost: i64,
+    /// Column assigned to each row.
+    pub matching: Vec<usize>,
+    /// Dual potentials with `u[i] + v[j] <= a[i][j]`, tight on the matching.
+    pub u: Vec<i64>,
+    pub v: Vec<i64>,
+}
+
+/// Minimum cost assignment of every row to a distinct column by the Hungarian method.
+/// Requires `n <= m` for an `n x m` cost matrix. O(n^2 m).
+pub fn hungarian(a: &Matrix<i64>) -> Assignment {
+    let (n, m) = a.shape();
+    assert!(n <= m);
+    // 1-indexed, with row 0 and column 0 as sentinels.
+    let mut u = vec![0; n + 1];
+    let mut v = vec![0; m + 1];
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut min = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        while p[j0] != 0 {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = i64::MAX;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = a[(i0 - 1, j - 1)] - u[i0] - v[j];
+                if cur < min[j] {
+                    min[j] = cur;
+                    way[j] = j0;
+                }
+                if min[j] < delta {
+                    delta = min[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min[j] -= delta;
+                }
+            }
+            j0 = j1;
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut matching = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            matching[p[j] - 1] = j - 1;
+        }
+    }
+    Assignment {
+        cost: -v[0],
+        matching,
+        u: u[1..].to_vec(),
+        v: v[1..].to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hungarian;
+    use crate::matrix::Matrix;
+    use rand::prelude::*;
+
+    fn brute_force(a: &Matrix<i64>, i: usize, used: &mut Vec<bool>) -> i64 {
+        let (n, m) = a.shape();
+        if i == n {
+            return 0;
+        }
+        let mut res = i64::MAX;
+        for j in 0..m {
+            if !used[j] {
+                used[j] = true;
+                res = res.min(a[(i, j)] + brute_force(a, i + 1, used));
+                used[j] = false;
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let a = Matrix::new(vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]]);
+        let res = hungarian(&a);
+        assert_eq!(res.cost, 5);
+        assert_eq!(res.matching, vec![1, 0, 2]);
+
+        let mut rng = thread_rng();
+        for _ in 0..300 {
+            let n = rng.gen_range(0..6);
+            let m = rng.gen_range(n..8);
+            let a = Matrix::from_fn(n, m, |_, _| rng.gen_range(-100..100));
+            let res = hungarian(&a);
+            assert_eq!(res.cost, brute_force(&a, 0, &mut vec![false; m]));
+
+            let mut cols = res.matching.clone();
+            cols.sort_unstable();
+            cols.dedup();
+            assert_eq!(cols.len(), n);
+            let (u, v) = (&res.u, &res.v);
+            assert_eq!(
+                (0..n).map(|i| a[(i, res.matching[i])]).sum::<i64>(),
+                res.cost
+            );
+            for i in 0..n {
+                for j in 0..m {
+                    assert!(u[i] + v[j] <= a[(i, j)]);
+                }
+                assert_eq!(u[i] + v[res.matching[i]], a[(i, res.matching[i])]);
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::number::Number;
+use cargo_snippet::snippet;
+
+#[snippet("Imos2D")]
+pub struct Imos2D<T> {
+    diff: Vec<Vec<T>>,
+    vec: Vec<Vec<T>>,
+    h: usize,
+    w: usize,
+}
+
+#[snippet("Imos2D")]
+impl<T> Imos2D<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            diff: vec![vec![T::zero(); w + 1]; h + 1],
+            vec: vec![vec![T::zero(); w]; h],
+            h,
+            w,
+        }
+    }
+
+    /// Adds `element` to `[x1, x2) x [y1, y2)`.
+    pub fn add(&mut self, x1: usize, x2: usize, y1: usize, y2: usize, element: T) {
+        self.diff[y1][x1] = self.diff[y1][x1] + element;
+        self.diff[y1][x2] = self.diff[y1][x2] - element;
+        self.diff[y2][x1] = self.diff[y2][x1] - element;
+        self.diff[y2][x2] = self.diff[y2][x2] + element;
+    }
+
+    pub fn build(&mut self) {
+        for y in 0..self.h {
+            for x in 0..self.w {
+                let mut v = self.diff[y][x];
+                if x > 0 {
+                    v = v + self.vec[y][x - 1];
+                }
+                if y > 0 {
+                    v = v + self.vec[y - 1][x];
+                }
+                if x > 0 && y > 0 {
+                    v = v - self.vec[y - 1][x - 1];
+                }
+                self.vec[y][x] = v;
+            }
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> T {
+        self.vec[y][x]
+    }
+}
+
+#[snippet("Imos1D")]
+pub struct Imos1D<T> {
+    diff: Vec<T>,
+    vec: Vec<T>,
+    len: usize,
+}
+
+#[snippet("Imos1D")]
+impl<T> Imos1D<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(len: usize) -> Self {
+        Self {
+            diff: vec![T::zero(); len + 1],
+            vec: vec![T::zero(); len],
+            len,
+        }
+    }
+
+    /// Adds `element` to `[l, r)`.
+    pub fn add(&mut self, l: usize, r: usize, element: T) {
+        self.diff[l] = self.diff[l] + element;
+        self.diff[r] = self.diff[r] - element;
+    }
+
+    pub fn build(&mut self) {
+        let mut acc = T::zero();
+        for i in 0..self.len {
+            acc = acc + self.diff[i];
+            self.vec[i] = acc;
+        }
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        self.vec[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Imos1D, Imos2D};
+    use rand::prelude::*;
+
+    #[test]
+    fn test_imos_1d() {
+        let mut imos = Imos1D::new(5);
+        imos.add(0, 3, 1);
+        imos.add(2, 5, 2);
+        imos.add(4, 4, 10);
+        imos.build();
+        let v = (0..5).map(|i| imos.get(i)).collect::<Vec<i64>>();
+        assert_eq!(v, vec![1, 1, 3, 2, 2]);
+    }
+
+    #[test]
+    fn test_imos_2d() {
+        let mut rng = thread_rng();
+        let (h, w) = (6, 8);
+        let mut imos = Imos2D::new(h, w);
+        let mut vec = vec![vec![0i64; w]; h];
+        for _ in 0..100 {
+            let x1 = rng.gen_range(0..=w);
+            let x2 = rng.gen_range(x1..=w);
+            let y1 = rng.gen_range(0..=h);
+            let y2 = rng.gen_range(y1..=h);
+            let e = rng.gen_range(-10..10);
+            imos.add(x1, x2, y1, y2, e);
+            for row in &mut vec[y1..y2] {
+                row[x1..x2].iter_mut().for_each(|v| *v += e);
+            }
+        }
+        imos.build();
+        imos.build();
+        for y in 0..h {
+            for x in 0..w {
+                assert_eq!(imos.get(x, y), vec[y][x]);
+            }
+        }
+    }
+}
@@ -7,4 +7,21 @@ pub mod util;
 pub mod dijkstra;
 pub mod prefix_sum;
 pub mod number;
-pub mod complex;
\ No newline at end of file
+pub mod complex;
+pub mod line;
+pub mod circle;
+pub mod point;
+pub mod half_plane;
+pub mod delaunay;
+pub mod vec3;
+pub mod union_area;
+pub mod modint;
+pub mod bit_matrix;
+pub mod semiring;
+pub mod simplex;
+pub mod hungarian;
+pub mod matrix_tree;
+pub mod smatrix;
+pub mod fenwick_tree;
+pub mod imos;
+pub mod segment_tree;
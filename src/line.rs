@@ -0,0 +1,66 @@
+use crate::complex::{Complex, EPS};
+use cargo_snippet::snippet;
+
+#[snippet(name = "Line", include = "Complex")]
+#[derive(Clone, Copy, Debug)]
+pub struct Line {
+    pub a: Complex,
+    pub b: Complex,
+}
+
+#[snippet("Line")]
+impl Line {
+    pub fn new(a: Complex, b: Complex) -> Self {
+        Line { a, b }
+    }
+
+    pub fn dir(&self) -> Complex {
+        self.b - self.a
+    }
+
+    /// Positive if `p` is on the left side of `a -> b`.
+    pub fn side(&self, p: Complex) -> f64 {
+        self.dir().cross(p - self.a)
+    }
+
+    pub fn projection(&self, p: Complex) -> Complex {
+        let d = self.dir();
+        self.a + d * ((p - self.a).dot(d) / d.norm())
+    }
+
+    pub fn distance(&self, p: Complex) -> f64 {
+        (self.projection(p) - p).abs()
+    }
+
+    pub fn is_parallel(&self, other: &Line) -> bool {
+        self.dir().cross(other.dir()).abs() < EPS
+    }
+
+    pub fn intersection(&self, other: &Line) -> Option<Complex> {
+        let d = self.dir().cross(other.dir());
+        if d.abs() < EPS {
+            return None;
+        }
+        let t = (other.a - self.a).cross(other.dir()) / d;
+        Some(self.a + self.dir() * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Line;
+    use crate::complex::Complex;
+
+    #[test]
+    fn test_line() {
+        let l = Line::new(Complex::new(0., 0.), Complex::new(2., 2.));
+        let m = Line::new(Complex::new(0., 2.), Complex::new(2., 0.));
+        let p = l.intersection(&m).unwrap();
+        assert!((p.x - 1.).abs() < 1e-9 && (p.y - 1.).abs() < 1e-9);
+        assert!((l.distance(Complex::new(0., 2.)) - 2f64.sqrt()).abs() < 1e-9);
+        assert!(l.side(Complex::new(0., 1.)) > 0.);
+        assert!(l
+            .intersection(&Line::new(Complex::new(1., 0.), Complex::new(2., 1.)))
+            .is_none());
+    }
+}
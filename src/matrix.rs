@@ -1,46 +1,499 @@
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
-use crate::number::Number;
+use crate::number::{Field, Number, Semiring};
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
 impl<T> Matrix<T> {
+    /// Panics if the rows have different lengths.
     pub fn new(v: Vec<Vec<T>>) -> Self {
+        let h = v.len();
+        let w = v.first().map_or(0, |row| row.len());
+        assert!(
+            v.iter().all(|row| row.len() == w),
+            "rows of a matrix must have the same length"
+        );
         Self {
-            h: v.len(),
-            w: v[0].len(),
-            v,
+            h,
+            w,
+            v: v.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn zeros(h: usize, w: usize) -> Self
+    where
+        T: Number + Clone,
+    {
+        Self {
+            h,
+            w,
+            v: vec![T::zero(); h * w],
         }
     }
 
+    pub fn from_fn(h: usize, w: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut v = Vec::with_capacity(h * w);
+        for i in 0..h {
+            for j in 0..w {
+                v.push(f(i, j));
+            }
+        }
+        Self { h, w, v }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.h, self.w)
+    }
+
     pub fn identity(n: usize) -> Self
     where
         T: Number + Clone,
     {
-        let mut v = vec![vec![T::zero(); n]; n];
+        let mut res = Self::zeros(n, n);
         for i in 0..n {
-            v[i][i] = T::one();
+            res[(i, i)] = T::one();
+        }
+
+        res
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.v[i * self.w..(i + 1) * self.w]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.v[i * self.w..(i + 1) * self.w]
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Copy,
+    {
+        let mut v = Vec::with_capacity(self.v.len());
+        for j in 0..self.w {
+            for i in 0..self.h {
+                v.push(self[(i, j)]);
+            }
+        }
+        Self {
+            h: self.w,
+            w: self.h,
+            v,
         }
+    }
 
-        Matrix::<T>::new(v)
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.w {
+            self.v.swap(a * self.w + j, b * self.w + j);
+        }
     }
 
-    pub fn mul_vec(&mut self, rhs: Vec<T>) -> Vec<T>
+    pub fn mul_vec(&self, rhs: Vec<T>) -> Vec<T>
     where
-        T: Mul<Output = T> + AddAssign + Number + Clone + Copy,
+        T: Semiring,
     {
-        let mut v = vec![T::zero(); rhs.len()];
+        assert_eq!(self.w, rhs.len(), "dimension mismatch in mul_vec");
+        let mut v = vec![T::zero(); self.h];
         for i in 0..self.h {
             for j in 0..self.w {
-                v[i] += self.v[i][j] * rhs[j];
+                v[i] += self[(i, j)] * rhs[j];
             }
         }
         v
     }
+
+    pub fn try_add(self, rhs: Self) -> Result<Self, DimensionMismatch>
+    where
+        T: AddAssign + Copy,
+    {
+        if self.shape() != rhs.shape() {
+            return Err(DimensionMismatch {
+                lhs: self.shape(),
+                rhs: rhs.shape(),
+            });
+        }
+        Ok(self + rhs)
+    }
+
+    pub fn try_mul(self, rhs: Self) -> Result<Self, DimensionMismatch>
+    where
+        T: Semiring,
+    {
+        if self.w != rhs.h {
+            return Err(DimensionMismatch {
+                lhs: self.shape(),
+                rhs: rhs.shape(),
+            });
+        }
+        Ok(self * rhs)
+    }
+
+    /// Strassen multiplication of square matrices, falling back to the naive
+    /// product at or below 64x64. Odd sizes are padded with zeros.
+    pub fn strassen_mul(&self, rhs: &Self) -> Self
+    where
+        T: Semiring + SubAssign,
+    {
+        const THRESHOLD: usize = 64;
+        assert!(self.h == self.w && rhs.shape() == self.shape());
+        let n = self.h;
+        if n <= THRESHOLD {
+            return self.clone() * rhs.clone();
+        }
+        let m = n.div_ceil(2);
+        let quad = |a: &Self, qi: usize, qj: usize| {
+            Self::from_fn(m, m, |i, j| {
+                let (r, c) = (qi * m + i, qj * m + j);
+                if r < n && c < n {
+                    a[(r, c)]
+                } else {
+                    T::zero()
+                }
+            })
+        };
+        let (a11, a12, a21, a22) = (
+            quad(self, 0, 0),
+            quad(self, 0, 1),
+            quad(self, 1, 0),
+            quad(self, 1, 1),
+        );
+        let (b11, b12, b21, b22) = (
+            quad(rhs, 0, 0),
+            quad(rhs, 0, 1),
+            quad(rhs, 1, 0),
+            quad(rhs, 1, 1),
+        );
+
+        let m1 = (a11.clone() + a22.clone()).strassen_mul(&(b11.clone() + b22.clone()));
+        let m2 = (a21.clone() + a22.clone()).strassen_mul(&b11);
+        let m3 = a11.strassen_mul(&(b12.clone() - b22.clone()));
+        let m4 = a22.strassen_mul(&(b21.clone() - b11.clone()));
+        let m5 = (a11.clone() + a12.clone()).strassen_mul(&b22);
+        let m6 = (a21 - a11).strassen_mul(&(b11 + b12));
+        let m7 = (a12 - a22).strassen_mul(&(b21 + b22));
+
+        let c11 = m1.clone() + m4.clone() - m5.clone() + m7;
+        let c12 = m3.clone() + m5;
+        let c21 = m2.clone() + m4;
+        let c22 = m1 - m2 + m3 + m6;
+        Self::from_fn(n, n, |i, j| {
+            let c = match (i < m, j < m) {
+                (true, true) => &c11,
+                (true, false) => &c12,
+                (false, true) => &c21,
+                (false, false) => &c22,
+            };
+            c[(i % m, j % m)]
+        })
+    }
+
+    pub fn pow(&self, mut n: u64) -> Self
+    where
+        T: Semiring,
+    {
+        assert_eq!(self.h, self.w);
+        let mut res = Matrix::identity(self.h);
+        let mut x = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                res *= x.clone();
+            }
+            n >>= 1;
+            if n > 0 {
+                x *= x.clone();
+            }
+        }
+        res
+    }
+
+    /// Companion matrix of `a[n] = c[0] * a[n - 1] + c[1] * a[n - 2] + ... + c[k - 1] * a[n - k]`.
+    pub fn companion(c: &[T]) -> Self
+    where
+        T: Number + Copy,
+    {
+        let k = c.len();
+        let mut res = Self::zeros(k, k);
+        res.row_mut(0).copy_from_slice(c);
+        for i in 1..k {
+            res[(i, i - 1)] = T::one();
+        }
+        res
+    }
+
+    /// Gauss-Jordan elimination on the first `cols` columns into reduced row echelon form.
+    /// Returns the pivot columns and the determinant of the eliminated part.
+    fn gauss_jordan(&mut self, cols: usize) -> (Vec<usize>, T)
+    where
+        T: Field,
+    {
+        let mut det = T::one();
+        let mut pivots = vec![];
+        for c in 0..cols {
+            let r = pivots.len();
+            if r == self.h {
+                break;
+            }
+            let p = (r..self.h)
+                .max_by(|&i, &j| {
+                    let (a, b) = (self[(i, c)].magnitude(), self[(j, c)].magnitude());
+                    a.partial_cmp(&b).unwrap()
+                })
+                .unwrap();
+            if self[(p, c)].magnitude() == 0. {
+                continue;
+            }
+            if p != r {
+                self.swap_rows(p, r);
+                det = T::zero() - det;
+            }
+            let x = self[(r, c)];
+            det = det * x;
+            for a in self.row_mut(r) {
+                *a = *a / x;
+            }
+            for i in 0..self.h {
+                let f = self[(i, c)];
+                if i == r || f == T::zero() {
+                    continue;
+                }
+                for j in 0..self.w {
+                    self.v[i * self.w + j] = self.v[i * self.w + j] - f * self.v[r * self.w + j];
+                }
+            }
+            pivots.push(c);
+        }
+        (pivots, det)
+    }
+
+    pub fn determinant(&self) -> T
+    where
+        T: Field,
+    {
+        assert_eq!(self.h, self.w);
+        let (pivots, det) = self.clone().gauss_jordan(self.w);
+        if pivots.len() < self.h {
+            T::zero()
+        } else {
+            det
+        }
+    }
+
+    pub fn rank(&self) -> usize
+    where
+        T: Field,
+    {
+        self.clone().gauss_jordan(self.w).0.len()
+    }
+
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Field,
+    {
+        assert_eq!(self.h, self.w);
+        let n = self.h;
+        let mut a = Self::zeros(n, 2 * n);
+        for i in 0..n {
+            a.row_mut(i)[..n].copy_from_slice(self.row(i));
+            a[(i, n + i)] = T::one();
+        }
+        if a.gauss_jordan(n).0.len() < n {
+            return None;
+        }
+        let mut res = Self {
+            h: n,
+            w: n,
+            v: Vec::with_capacity(n * n),
+        };
+        for i in 0..n {
+            res.v.extend_from_slice(&a.row(i)[n..]);
+        }
+        Some(res)
+    }
+
+    /// Solves `self * x = b`. Returns a particular solution and a basis of the kernel,
+    /// or `None` if there is no solution.
+    pub fn solve(&self, b: &[T]) -> Option<(Vec<T>, Vec<Vec<T>>)>
+    where
+        T: Field,
+    {
+        assert_eq!(self.h, b.len());
+        let w = self.w;
+        let mut a = Self {
+            h: self.h,
+            w: w + 1,
+            v: Vec::with_capacity(self.h * (w + 1)),
+        };
+        for i in 0..self.h {
+            a.v.extend_from_slice(self.row(i));
+            a.v.push(b[i]);
+        }
+        let (pivots, _) = a.gauss_jordan(w);
+        if (pivots.len()..self.h).any(|i| a[(i, w)].magnitude() != 0.) {
+            return None;
+        }
+
+        let mut x = vec![T::zero(); w];
+        let mut is_pivot = vec![false; w];
+        for (r, &c) in pivots.iter().enumerate() {
+            x[c] = a[(r, w)];
+            is_pivot[c] = true;
+        }
+        let mut kernel = vec![];
+        for f in (0..w).filter(|&c| !is_pivot[c]) {
+            let mut k = vec![T::zero(); w];
+            k[f] = T::one();
+            for (r, &c) in pivots.iter().enumerate() {
+                k[c] = T::zero() - a[(r, f)];
+            }
+            kernel.push(k);
+        }
+        Some((x, kernel))
+    }
+
+    /// Coefficients of `det(xI - self)` from the constant term, in O(n^3)
+    /// by reduction to upper Hessenberg form.
+    pub fn characteristic_polynomial(&self) -> Vec<T>
+    where
+        T: Field,
+    {
+        assert_eq!(self.h, self.w);
+        let n = self.h;
+        let mut a = self.clone();
+        for j in 0..n.saturating_sub(2) {
+            let p = match (j + 1..n).find(|&i| a[(i, j)] != T::zero()) {
+                Some(p) => p,
+                None => continue,
+            };
+            if p != j + 1 {
+                a.swap_rows(p, j + 1);
+                for i in 0..n {
+                    a.v.swap(i * n + p, i * n + j + 1);
+                }
+            }
+            let inv = T::one() / a[(j + 1, j)];
+            for i in j + 2..n {
+                let u = a[(i, j)] * inv;
+                if u == T::zero() {
+                    continue;
+                }
+                for k in 0..n {
+                    a[(i, k)] = a[(i, k)] - u * a[(j + 1, k)];
+                }
+                for k in 0..n {
+                    a[(k, j + 1)] = a[(k, j + 1)] + u * a[(k, i)];
+                }
+            }
+        }
+
+        let mut p = vec![vec![T::one()]];
+        for k in 0..n {
+            let mut next = vec![T::zero(); k + 2];
+            for i in 0..=k {
+                next[i + 1] = next[i + 1] + p[k][i];
+                next[i] = next[i] - a[(k, k)] * p[k][i];
+            }
+            let mut t = T::one();
+            for i in (0..k).rev() {
+                t = t * a[(i + 1, i)];
+                let c = t * a[(i, k)];
+                for j in 0..=i {
+                    next[j] = next[j] - c * p[i][j];
+                }
+            }
+            p.push(next);
+        }
+        p.pop().unwrap()
+    }
 }
 
+impl Matrix<i64> {
+    /// Determinant modulo an arbitrary `m` by Euclid-style row reduction,
+    /// without division. O(n^3 log m).
+    pub fn determinant_mod(&self, m: i64) -> i64 {
+        assert_eq!(self.h, self.w);
+        let n = self.h;
+        let mut a = self.clone();
+        for x in a.v.iter_mut() {
+            *x = x.rem_euclid(m);
+        }
+        let mut det = 1 % m;
+        for c in 0..n {
+            for i in c + 1..n {
+                while a[(i, c)] != 0 {
+                    let q = a[(c, c)] / a[(i, c)];
+                    for j in c..n {
+                        let x = a[(c, j)] as i128 - q as i128 * a[(i, j)] as i128;
+                        a[(c, j)] = x.rem_euclid(m as i128) as i64;
+                    }
+                    a.swap_rows(c, i);
+                    det = (m - det) % m;
+                }
+            }
+            det = (det as i128 * a[(c, c)] as i128 % m as i128) as i64;
+            if det == 0 {
+                break;
+            }
+        }
+        det
+    }
+}
+
+/// Returns `a[n]` of the linear recurrence with coefficients `c` and initial terms
+/// `a[0], ..., a[k - 1]` in O(k^3 log n).
+pub fn linear_recurrence<T>(c: &[T], init: &[T], n: u64) -> T
+where
+    T: Semiring,
+{
+    let k = c.len();
+    assert_eq!(init.len(), k);
+    if n < k as u64 {
+        return init[n as usize];
+    }
+    let m = Matrix::companion(c).pow(n - k as u64 + 1);
+    let mut res = T::zero();
+    for j in 0..k {
+        res += m[(0, j)] * init[k - 1 - j];
+    }
+    res
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionMismatch {
+    pub lhs: (usize, usize),
+    pub rhs: (usize, usize),
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "dimension mismatch: {}x{} and {}x{}",
+            self.lhs.0, self.lhs.1, self.rhs.0, self.rhs.1
+        )
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+/// Dense matrix stored in row-major order.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix<T> {
     h: usize,
     w: usize,
-    v: Vec<Vec<T>>,
+    v: Vec<T>,
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(j < self.w, "column {} out of range for width {}", j, self.w);
+        &self.v[i * self.w + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(j < self.w, "column {} out of range for width {}", j, self.w);
+        &mut self.v[i * self.w + j]
+    }
 }
 
 impl<T> AddAssign for Matrix<T>
@@ -48,10 +501,9 @@ where
     T: AddAssign + Copy,
 {
     fn add_assign(&mut self, rhs: Self) {
-        for i in 0..self.h {
-            for j in 0..self.w {
-                self.v[i][j] += rhs.v[i][j];
-            }
+        assert_eq!(self.shape(), rhs.shape(), "dimension mismatch in add");
+        for (a, &b) in self.v.iter_mut().zip(&rhs.v) {
+            *a += b;
         }
     }
 }
@@ -61,28 +513,34 @@ where
     T: SubAssign + Copy,
 {
     fn sub_assign(&mut self, rhs: Self) {
-        for i in 0..self.h {
-            for j in 0..self.w {
-                self.v[i][j] -= rhs.v[i][j];
-            }
+        assert_eq!(self.shape(), rhs.shape(), "dimension mismatch in sub");
+        for (a, &b) in self.v.iter_mut().zip(&rhs.v) {
+            *a -= b;
         }
     }
 }
 
 impl<T> MulAssign for Matrix<T>
 where
-    T: AddAssign + Mul<Output = T> + Number + Copy,
+    T: Semiring,
 {
     fn mul_assign(&mut self, rhs: Self) {
-        let mut v = vec![vec![T::zero(); rhs.w]; self.h];
+        assert_eq!(self.w, rhs.h, "dimension mismatch in mul");
+        let mut v = vec![T::zero(); self.h * rhs.w];
         for i in 0..self.h {
-            for j in 0..rhs.w {
-                for k in 0..rhs.h {
-                    v[i][j] += self.v[i][k] * rhs.v[k][j];
+            let out = &mut v[i * rhs.w..(i + 1) * rhs.w];
+            for k in 0..self.w {
+                let a = self.v[i * self.w + k];
+                for (c, &b) in out.iter_mut().zip(rhs.row(k)) {
+                    *c += a * b;
                 }
             }
         }
-        *self = Matrix::new(v);
+        *self = Self {
+            h: self.h,
+            w: rhs.w,
+            v,
+        };
     }
 }
 
@@ -100,7 +558,7 @@ where
 
 impl<T> Mul for Matrix<T>
 where
-    T: AddAssign + Mul<Output = T> + Number + Copy,
+    T: Semiring,
 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
@@ -124,7 +582,9 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::matrix::Matrix;
+    use crate::matrix::{linear_recurrence, DimensionMismatch, Matrix};
+    use crate::modint::ModInt998244353;
+    use rand::prelude::*;
 
     #[test]
     fn test_matrix_add() {}
@@ -167,4 +627,313 @@ mod test {
 
     #[test]
     fn test_identity() {}
+
+    #[test]
+    fn test_pow() {
+        let a = Matrix::new(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(a.pow(0), Matrix::identity(2));
+        assert_eq!(a.pow(1), a);
+        assert_eq!(a.pow(10), Matrix::new(vec![vec![89, 55], vec![55, 34]]));
+
+        let a = Matrix::new(vec![vec![2, 1, 0], vec![0, 1, 3], vec![1, 0, 1]]);
+        let mut b = Matrix::identity(3);
+        for n in 0..10 {
+            assert_eq!(a.pow(n), b);
+            b *= a.clone();
+        }
+    }
+
+    #[test]
+    fn test_linear_recurrence() {
+        let mut fib = vec![0i64, 1];
+        for i in 2..90 {
+            fib.push(fib[i - 1] + fib[i - 2]);
+        }
+        for n in 0..90 {
+            assert_eq!(linear_recurrence(&[1, 1], &[0, 1], n), fib[n as usize]);
+        }
+
+        // a[n] = a[n - 1] + 2 a[n - 2] - a[n - 3]
+        let mut a = vec![1i64, 2, 3];
+        for i in 3..40 {
+            a.push(a[i - 1] + 2 * a[i - 2] - a[i - 3]);
+        }
+        for n in 0..40 {
+            assert_eq!(linear_recurrence(&[1, 2, -1], &[1, 2, 3], n), a[n as usize]);
+        }
+    }
+
+    #[test]
+    fn test_determinant() {
+        let a = Matrix::new(vec![
+            vec![2., -1., 0.],
+            vec![-1., 2., -1.],
+            vec![0., -1., 2.],
+        ]);
+        assert!((a.determinant() - 4.).abs() < 1e-9);
+        let a = Matrix::new(vec![vec![0., 1.], vec![1., 0.]]);
+        assert!((a.determinant() + 1.).abs() < 1e-9);
+        let a = Matrix::new(vec![vec![1., 2.], vec![2., 4.]]);
+        assert_eq!(a.determinant(), 0.);
+
+        type Mint = ModInt998244353;
+        let m = |v: Vec<Vec<i64>>| {
+            Matrix::new(
+                v.into_iter()
+                    .map(|r| r.into_iter().map(Mint::new).collect())
+                    .collect(),
+            )
+        };
+        let a = m(vec![vec![0, 3, 1], vec![2, 0, 5], vec![1, 4, 0]]);
+        assert_eq!(a.determinant(), Mint::new(23));
+    }
+
+    #[test]
+    fn test_rank() {
+        let a = Matrix::new(vec![vec![1., 2., 3.], vec![2., 4., 6.], vec![1., 0., 1.]]);
+        assert_eq!(a.rank(), 2);
+        let a = Matrix::new(vec![vec![1., 2.], vec![3., 4.], vec![5., 6.]]);
+        assert_eq!(a.rank(), 2);
+        let a = Matrix::new(vec![vec![0., 0.], vec![0., 0.]]);
+        assert_eq!(a.rank(), 0);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = Matrix::new(vec![vec![4., 7.], vec![2., 6.]]);
+        let b = a.inverse().unwrap();
+        let c = b.clone() * a.clone();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((c[(i, j)] - if i == j { 1. } else { 0. }).abs() < 1e-9);
+            }
+        }
+        assert!(Matrix::new(vec![vec![1., 2.], vec![2., 4.]])
+            .inverse()
+            .is_none());
+
+        type Mint = ModInt998244353;
+        let a = Matrix::new(vec![
+            vec![Mint::new(1), Mint::new(2), Mint::new(0)],
+            vec![Mint::new(0), Mint::new(1), Mint::new(3)],
+            vec![Mint::new(4), Mint::new(0), Mint::new(1)],
+        ]);
+        assert_eq!(a.inverse().unwrap() * a, Matrix::identity(3));
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = Matrix::new(vec![vec![2., 1.], vec![1., 3.]]);
+        let (x, kernel) = a.solve(&[3., 5.]).unwrap();
+        assert!((x[0] - 0.8).abs() < 1e-9 && (x[1] - 1.4).abs() < 1e-9);
+        assert!(kernel.is_empty());
+
+        type Mint = ModInt998244353;
+        let m = |v: Vec<i64>| v.into_iter().map(Mint::new).collect::<Vec<_>>();
+        let a = Matrix::new(vec![m(vec![1, 2, 3]), m(vec![2, 4, 6]), m(vec![1, 0, 1])]);
+        let b = m(vec![6, 12, 2]);
+        let (x, kernel) = a.solve(&b).unwrap();
+        assert_eq!(kernel.len(), 1);
+        assert_eq!(a.clone().mul_vec(x.clone()), b);
+        for k in kernel {
+            let y = x
+                .iter()
+                .zip(&k)
+                .map(|(&a, &b)| a + b * Mint::new(5))
+                .collect();
+            assert_eq!(a.clone().mul_vec(y), b);
+        }
+        assert!(a.solve(&m(vec![6, 13, 2])).is_none());
+    }
+
+    #[test]
+    fn test_index_and_transpose() {
+        let mut a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(a[(1, 2)], 6);
+        a[(0, 1)] = 7;
+        assert_eq!(a.row(0), &[1, 7, 3]);
+        a.row_mut(1)[0] = 8;
+        assert_eq!(
+            a.transpose(),
+            Matrix::new(vec![vec![1, 8], vec![7, 5], vec![3, 6]])
+        );
+    }
+
+    #[test]
+    fn test_mul_random() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let (h, m, w) = (
+                rng.gen_range(1..10),
+                rng.gen_range(1..10),
+                rng.gen_range(1..10),
+            );
+            let a = (0..h)
+                .map(|_| (0..m).map(|_| rng.gen_range(-9..10)).collect::<Vec<i64>>())
+                .collect::<Vec<_>>();
+            let b = (0..m)
+                .map(|_| (0..w).map(|_| rng.gen_range(-9..10)).collect::<Vec<i64>>())
+                .collect::<Vec<_>>();
+            let c = (0..h)
+                .map(|i| {
+                    (0..w)
+                        .map(|j| (0..m).map(|k| a[i][k] * b[k][j]).sum())
+                        .collect::<Vec<i64>>()
+                })
+                .collect::<Vec<_>>();
+            let (a, b) = (Matrix::new(a), Matrix::new(b));
+            assert_eq!(a.clone() * b.clone(), Matrix::new(c.clone()));
+            assert_eq!(b.transpose() * a.transpose(), Matrix::new(c).transpose());
+        }
+    }
+
+    #[test]
+    fn test_constructors() {
+        let a = Matrix::<i64>::new(vec![]);
+        assert_eq!(a.shape(), (0, 0));
+        let a = Matrix::<i64>::new(vec![vec![], vec![]]);
+        assert_eq!(a.shape(), (2, 0));
+        assert_eq!(Matrix::<i64>::zeros(2, 3), Matrix::new(vec![vec![0; 3]; 2]));
+        assert_eq!(
+            Matrix::from_fn(2, 3, |i, j| (i * 3 + j) as i64),
+            Matrix::new(vec![vec![0, 1, 2], vec![3, 4, 5]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_jagged() {
+        Matrix::new(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_try_ops() {
+        let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::new(vec![vec![1, 0], vec![0, 1]]);
+        assert_eq!(
+            a.clone().try_add(b.clone()),
+            Err(DimensionMismatch {
+                lhs: (2, 3),
+                rhs: (2, 2)
+            })
+        );
+        assert_eq!(
+            a.clone().try_add(a.clone()),
+            Ok(Matrix::new(vec![vec![2, 4, 6], vec![8, 10, 12]]))
+        );
+        assert!(a.clone().try_mul(b.clone()).is_err());
+        assert_eq!(b.try_mul(a.clone()), Ok(a.clone()));
+        assert_eq!(a.mul_vec(vec![1, 1, 1]), vec![6, 15]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_mismatch() {
+        let _ = Matrix::new(vec![vec![1, 2]]) * Matrix::new(vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        type Mint = ModInt998244353;
+        let a = Matrix::new(vec![vec![2., 1.], vec![1., 2.]]);
+        let p = a.characteristic_polynomial();
+        assert_eq!(p.len(), 3);
+        for (x, y) in p.iter().zip(&[3., -4., 1.]) {
+            assert!((x - y).abs() < 1e-9);
+        }
+
+        let mut rng = thread_rng();
+        for n in 0..8 {
+            let a = Matrix::from_fn(n, n, |_, _| {
+                if rng.gen_bool(0.3) {
+                    Mint::new(0)
+                } else {
+                    Mint::new(rng.gen_range(0..10))
+                }
+            });
+            let p = a.characteristic_polynomial();
+            assert_eq!(p.len(), n + 1);
+            for _ in 0..5 {
+                let x = Mint::new(rng.gen_range(0..1_000_000_000));
+                let value = p.iter().rev().fold(Mint::new(0), |acc, &c| acc * x + c);
+                let xi = Matrix::from_fn(n, n, |i, j| if i == j { x } else { Mint::new(0) });
+                let mut b = xi;
+                b -= a.clone();
+                assert_eq!(
+                    value,
+                    if n == 0 {
+                        Mint::new(1)
+                    } else {
+                        b.determinant()
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_determinant_mod() {
+        fn brute(a: &[Vec<i64>], m: i64) -> i64 {
+            let n = a.len();
+            if n == 0 {
+                return 1 % m;
+            }
+            let mut res = 0;
+            for j in 0..n {
+                let minor = a[1..]
+                    .iter()
+                    .map(|r| [&r[..j], &r[j + 1..]].concat())
+                    .collect::<Vec<_>>();
+                let sign = if j % 2 == 0 { 1 } else { -1 };
+                res += sign * a[0][j] % m * brute(&minor, m);
+                res %= m;
+            }
+            res.rem_euclid(m)
+        }
+
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(0..6);
+            let m = rng.gen_range(1..1000);
+            let v = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| rng.gen_range(-100..100))
+                        .collect::<Vec<i64>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(Matrix::new(v.clone()).determinant_mod(m), brute(&v, m));
+        }
+        let a = Matrix::new(vec![vec![2, 1], vec![1, 2]]);
+        assert_eq!(a.determinant_mod(1_000_000_000_000_000_000), 3);
+    }
+
+    #[test]
+    fn test_strassen_mul() {
+        type Mint = ModInt998244353;
+        let mut rng = thread_rng();
+        for &n in &[1, 64, 65, 131, 150] {
+            let a = Matrix::from_fn(n, n, |_, _| Mint::new(rng.gen_range(0..1_000_000_000)));
+            let b = Matrix::from_fn(n, n, |_, _| Mint::new(rng.gen_range(0..1_000_000_000)));
+            assert_eq!(a.strassen_mul(&b), a * b);
+        }
+        let a = Matrix::from_fn(100, 100, |_, _| rng.gen_range(-100..100));
+        let b = Matrix::from_fn(100, 100, |_, _| rng.gen_range(-100..100));
+        assert_eq!(a.strassen_mul(&b), a * b);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_row() {
+        let a = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+        let _ = a[(0, 2)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_out_of_row() {
+        let mut a = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+        a[(0, 2)] = 5;
+    }
 }
@@ -0,0 +1,136 @@
+use crate::matrix::Matrix;
+
+/// Laplacian of a multigraph on `n` vertices. Self-loops are ignored.
+/// If `directed`, an edge `(u, v)` goes from `u` to `v` and the diagonal holds in-degrees.
+pub fn laplacian(n: usize, edges: &[(usize, usize)], directed: bool) -> Matrix<i64> {
+    let mut l = Matrix::zeros(n, n);
+    for &(u, v) in edges {
+        if u == v {
+            continue;
+        }
+        l[(v, v)] += 1;
+        l[(u, v)] -= 1;
+        if !directed {
+            l[(u, u)] += 1;
+            l[(v, u)] -= 1;
+        }
+    }
+    l
+}
+
+fn minor_determinant(l: &Matrix<i64>, root: usize, m: i64) -> i64 {
+    let n = l.shape().0;
+    let skip = |i: usize| if i < root { i } else { i + 1 };
+    Matrix::from_fn(n - 1, n - 1, |i, j| l[(skip(i), skip(j))]).determinant_mod(m)
+}
+
+/// Number of spanning trees of an undirected multigraph modulo `m`
+/// by Kirchhoff's matrix-tree theorem. O(n^3 log m).
+pub fn count_spanning_trees(n: usize, edges: &[(usize, usize)], m: i64) -> i64 {
+    assert!(n > 0);
+    minor_determinant(&laplacian(n, edges, false), 0, m)
+}
+
+/// Number of spanning arborescences with all edges directed away from `root`
+/// modulo `m`. O(n^3 log m).
+pub fn count_arborescences(n: usize, edges: &[(usize, usize)], root: usize, m: i64) -> i64 {
+    assert!(root < n);
+    minor_determinant(&laplacian(n, edges, true), root, m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_arborescences, count_spanning_trees};
+    use crate::union_find::UnionFind;
+    use rand::prelude::*;
+
+    const MOD: i64 = 998244353;
+
+    fn subsets(edges: &[(usize, usize)], k: usize) -> Vec<Vec<(usize, usize)>> {
+        (0u32..1 << edges.len())
+            .filter(|s| s.count_ones() as usize == k)
+            .map(|s| {
+                (0..edges.len())
+                    .filter(|&i| s >> i & 1 == 1)
+                    .map(|i| edges[i])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_count_spanning_trees() {
+        let complete = |n: usize| {
+            (0..n)
+                .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(count_spanning_trees(1, &[], MOD), 1);
+        assert_eq!(count_spanning_trees(2, &[(0, 1), (1, 0), (0, 1)], MOD), 3);
+        assert_eq!(count_spanning_trees(3, &[(0, 1), (1, 1)], MOD), 0);
+        assert_eq!(count_spanning_trees(5, &complete(5), MOD), 125);
+        assert_eq!(count_spanning_trees(5, &complete(5), 7), 125 % 7);
+        let cycle = (0..10).map(|i| (i, (i + 1) % 10)).collect::<Vec<_>>();
+        assert_eq!(count_spanning_trees(10, &cycle, MOD), 10);
+        let k100 = complete(100);
+        let expected = (0..98).fold(1, |x, _| x * 100 % MOD);
+        assert_eq!(count_spanning_trees(100, &k100, MOD), expected);
+
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1..6);
+            let edges = (0..rng.gen_range(0..10))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect::<Vec<_>>();
+            let expected = subsets(&edges, n - 1)
+                .iter()
+                .filter(|es| {
+                    let mut uf = UnionFind::new(n);
+                    es.iter().all(|&(u, v)| uf.unite(u, v))
+                })
+                .count() as i64;
+            assert_eq!(count_spanning_trees(n, &edges, MOD), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_arborescences() {
+        let complete = (0..4)
+            .flat_map(|u| (0..4).map(move |v| (u, v)))
+            .collect::<Vec<_>>();
+        assert_eq!(count_arborescences(4, &complete, 2, MOD), 16);
+        assert_eq!(count_arborescences(3, &[(0, 1), (1, 2)], 0, MOD), 1);
+        assert_eq!(count_arborescences(3, &[(0, 1), (1, 2)], 1, MOD), 0);
+
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1..6);
+            let root = rng.gen_range(0..n);
+            let edges = (0..rng.gen_range(0..12))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect::<Vec<_>>();
+            let expected = subsets(&edges, n - 1)
+                .iter()
+                .filter(|es| {
+                    let mut parent = vec![None; n];
+                    for &(u, v) in es.iter() {
+                        if v == root || parent[v].is_some() {
+                            return false;
+                        }
+                        parent[v] = Some(u);
+                    }
+                    (0..n).all(|mut v| {
+                        for _ in 0..n {
+                            match parent[v] {
+                                Some(u) => v = u,
+                                None => break,
+                            }
+                        }
+                        v == root
+                    })
+                })
+                .count() as i64;
+            assert_eq!(count_arborescences(n, &edges, root, MOD), expected);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::number::{Field, Number};
+use cargo_snippet::snippet;
+#[snippet("ModInt")]
+use std::fmt;
+#[snippet("ModInt")]
+use std::ops::*;
+
+#[snippet("ModInt")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u32> {
+    v: u32,
+}
+
+#[snippet("ModInt")]
+pub type ModInt998244353 = ModInt<998244353>;
+#[snippet("ModInt")]
+pub type ModInt1000000007 = ModInt<1000000007>;
+
+#[snippet("ModInt")]
+impl<const M: u32> ModInt<M> {
+    pub fn new(v: i64) -> Self {
+        Self {
+            v: v.rem_euclid(M as i64) as u32,
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.v
+    }
+
+    pub fn modulus() -> u32 {
+        M
+    }
+
+    pub fn pow(self, mut n: u64) -> Self {
+        let mut res = Self::new(1);
+        let mut x = self;
+        while n > 0 {
+            if n & 1 == 1 {
+                res *= x;
+            }
+            x *= x;
+            n >>= 1;
+        }
+        res
+    }
+
+    /// Inverse by the extended Euclidean algorithm. Panics if not coprime to the modulus.
+    pub fn inv(self) -> Self {
+        let (mut a, mut b) = (self.v as i64, M as i64);
+        let (mut x, mut y) = (1i64, 0i64);
+        while b != 0 {
+            let t = a / b;
+            a -= t * b;
+            x -= t * y;
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut x, &mut y);
+        }
+        assert_eq!(a, 1, "{} is not invertible modulo {}", self.v, M);
+        Self::new(x)
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> From<i64> for ModInt<M> {
+    fn from(v: i64) -> Self {
+        Self::new(v)
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.v)
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> Add for ModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let v = self.v as u64 + rhs.v as u64;
+        Self {
+            v: if v >= M as u64 { v - M as u64 } else { v } as u32,
+        }
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> Sub for ModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let v = self.v as u64 + M as u64 - rhs.v as u64;
+        Self {
+            v: if v >= M as u64 { v - M as u64 } else { v } as u32,
+        }
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> Mul for ModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            v: (self.v as u64 * rhs.v as u64 % M as u64) as u32,
+        }
+    }
+}
+
+#[snippet("ModInt")]
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const M: u32> Div for ModInt<M> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> Neg for ModInt<M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(0) - self
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[snippet("ModInt")]
+impl<const M: u32> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const M: u32> Number for ModInt<M> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const M: u32> Field for ModInt<M> {
+    fn magnitude(&self) -> f64 {
+        if self.v == 0 {
+            0.
+        } else {
+            1.
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModInt, ModInt1000000007, ModInt998244353};
+
+    #[test]
+    fn test_modint() {
+        type Mint = ModInt998244353;
+        let a = Mint::new(-1);
+        assert_eq!(a.value(), 998244352);
+        assert_eq!((a + Mint::new(2)).value(), 1);
+        assert_eq!((Mint::new(1) - Mint::new(2)).value(), 998244352);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!(Mint::new(3) / Mint::new(3), Mint::new(1));
+        assert_eq!(Mint::new(2).pow(23) * Mint::new(119), Mint::new(-1));
+        assert_eq!(ModInt1000000007::new(2).inv().value(), 500000004);
+        assert_eq!(format!("{}", -Mint::new(5)), "998244348");
+
+        type Mod10 = ModInt<10>;
+        assert_eq!(Mod10::new(3).inv(), Mod10::new(7));
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::{Add, AddAssign, Div, Mul, Sub};
+
 pub trait Number {
     fn zero() -> Self;
     fn one() -> Self;
@@ -22,3 +24,31 @@ impl Number for f64 {
         1_f64
     }
 }
+
+/// Types whose `+=` and `*` form a semiring with `zero` and `one` as identities.
+pub trait Semiring: Number + Copy + AddAssign + Mul<Output = Self> {}
+
+impl<T: Number + Copy + AddAssign + Mul<Output = T>> Semiring for T {}
+
+pub trait Field:
+    Number
+    + Copy
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// Used to choose pivots in elimination. Zero if the element must not be a pivot.
+    fn magnitude(&self) -> f64;
+}
+
+impl Field for f64 {
+    fn magnitude(&self) -> f64 {
+        if self.abs() < 1e-9 {
+            0.
+        } else {
+            self.abs()
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use cargo_snippet::snippet;
+#[snippet("Point")]
+use std::cmp::Ordering;
+#[snippet("Point")]
+use std::ops::{Add, Sub};
+
+#[snippet("Point")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[snippet("Point")]
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn dot(&self, rhs: Self) -> i64 {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    pub fn cross(&self, rhs: Self) -> i64 {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    pub fn norm(&self) -> i64 {
+        self.dot(*self)
+    }
+
+    /// 0 for arguments in [0, pi), 1 for [pi, 2pi).
+    fn half(&self) -> u8 {
+        (self.y < 0 || (self.y == 0 && self.x < 0)) as u8
+    }
+
+    /// Compares arguments in [0, 2pi) exactly. The origin compares less than every other point.
+    pub fn arg_cmp(&self, other: &Self) -> Ordering {
+        let key = |p: &Self| (p.half(), *p != Point::new(0, 0));
+        key(self)
+            .cmp(&key(other))
+            .then_with(|| 0.cmp(&self.cross(*other)))
+    }
+}
+
+#[snippet("Point")]
+impl Add for Point {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+#[snippet("Point")]
+impl Sub for Point {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[snippet(name = "arg_sort", include = "Point")]
+pub fn arg_sort(ps: &mut [Point]) {
+    ps.sort_by(|a, b| a.arg_cmp(b));
+}
+
+/// Returns `(squared distance, i, j)` of the closest pair in O(n log n).
+#[snippet(name = "closest_pair", include = "Point")]
+pub fn closest_pair(ps: &[Point]) -> Option<(i64, usize, usize)> {
+    fn rec(ps: &mut [(Point, usize)], best: &mut (i64, usize, usize)) {
+        let n = ps.len();
+        if n <= 1 {
+            return;
+        }
+        let m = n / 2;
+        let mid_x = ps[m].0.x;
+        rec(&mut ps[..m], best);
+        rec(&mut ps[m..], best);
+
+        let mut merged = Vec::with_capacity(n);
+        let (mut i, mut j) = (0, m);
+        while i < m || j < n {
+            if j == n || (i < m && ps[i].0.y <= ps[j].0.y) {
+                merged.push(ps[i]);
+                i += 1;
+            } else {
+                merged.push(ps[j]);
+                j += 1;
+            }
+        }
+        ps.copy_from_slice(&merged);
+
+        let mut strip: Vec<(Point, usize)> = vec![];
+        for &(p, i) in ps.iter() {
+            let dx = p.x - mid_x;
+            if dx * dx >= best.0 {
+                continue;
+            }
+            for &(q, j) in strip.iter().rev() {
+                let dy = p.y - q.y;
+                if dy * dy >= best.0 {
+                    break;
+                }
+                let d = (p - q).norm();
+                if d < best.0 {
+                    *best = (d, j.min(i), j.max(i));
+                }
+            }
+            strip.push((p, i));
+        }
+    }
+
+    if ps.len() < 2 {
+        return None;
+    }
+    let mut v = ps.iter().copied().zip(0..).collect::<Vec<_>>();
+    v.sort_by_key(|&(p, _)| p.x);
+    let mut best = (i64::MAX, 0, 0);
+    rec(&mut v, &mut best);
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arg_sort, closest_pair, Point};
+
+    #[test]
+    fn test_arg_sort() {
+        let mut ps = vec![
+            Point::new(-1, -1),
+            Point::new(0, -3),
+            Point::new(1, 0),
+            Point::new(-2, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(2, 2),
+            Point::new(1, -1),
+            Point::new(-1, 1),
+            Point::new(0, 0),
+        ];
+        arg_sort(&mut ps);
+        let xs = ps.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(&xs[..3], &[(0, 0), (1, 0), (1, 1)][..]);
+        assert!(xs[3] == (1, 1) || xs[3] == (2, 2));
+        assert_eq!(
+            &xs[4..],
+            &[(0, 1), (-1, 1), (-2, 0), (-1, -1), (0, -3), (1, -1)][..]
+        );
+
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let mut ps = (0..rng.gen_range(1..30))
+                .map(|_| Point::new(rng.gen_range(-2..=2), rng.gen_range(-2..=2)))
+                .collect::<Vec<_>>();
+            ps.push(Point::new(0, 0));
+            arg_sort(&mut ps);
+            assert_eq!(ps[0], Point::new(0, 0));
+            let arg = |p: &Point| {
+                let a = (p.y as f64).atan2(p.x as f64);
+                if a < 0. {
+                    a + 2. * std::f64::consts::PI
+                } else {
+                    a
+                }
+            };
+            for w in ps.windows(2) {
+                if w[0] != Point::new(0, 0) {
+                    assert!(arg(&w[0]) <= arg(&w[1]) + 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_pair() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(2..200);
+            let ps = (0..n)
+                .map(|_| Point::new(rng.gen_range(-1000..1000), rng.gen_range(-1000..1000)))
+                .collect::<Vec<_>>();
+            let mut naive = i64::MAX;
+            for i in 0..n {
+                for j in i + 1..n {
+                    naive = naive.min((ps[i] - ps[j]).norm());
+                }
+            }
+            let (d, i, j) = closest_pair(&ps).unwrap();
+            assert_eq!(d, naive);
+            assert!(i < j);
+            assert_eq!((ps[i] - ps[j]).norm(), d);
+        }
+        assert_eq!(closest_pair(&[Point::new(0, 0)]), None);
+    }
+}
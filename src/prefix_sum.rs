@@ -115,6 +115,103 @@ where
     }
 }
 
+#[snippet("PrefixSumND")]
+pub struct PrefixSumND<T> {
+    sum: Vec<T>,
+    vec: Vec<T>,
+    shape: Vec<usize>,
+    // strides of `sum`, whose shape is `shape[d] + 1` in each dimension
+    strides: Vec<usize>,
+}
+
+#[snippet("PrefixSumND")]
+impl<T> PrefixSumND<T>
+where
+    T: Number + Clone + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(shape: &[usize]) -> Self {
+        let len = shape.iter().product();
+        Self::from_vec(shape, vec![T::zero(); len])
+    }
+
+    /// `vec` holds the elements in row-major order.
+    pub fn from_vec(shape: &[usize], vec: Vec<T>) -> Self {
+        assert_eq!(vec.len(), shape.iter().product::<usize>());
+        let mut strides = vec![1; shape.len()];
+        for d in (1..shape.len()).rev() {
+            strides[d - 1] = strides[d] * (shape[d] + 1);
+        }
+        let mut slf = Self {
+            sum: vec![T::zero(); shape.iter().map(|s| s + 1).product()],
+            vec,
+            shape: shape.to_vec(),
+            strides,
+        };
+        slf.build();
+        slf
+    }
+
+    fn offset(&self, idx: &[usize]) -> usize {
+        assert_eq!(idx.len(), self.shape.len());
+        idx.iter().zip(&self.shape).fold(0, |k, (&i, &s)| {
+            assert!(i < s);
+            k * s + i
+        })
+    }
+
+    pub fn add(&mut self, idx: &[usize], element: T) {
+        let k = self.offset(idx);
+        self.vec[k] = self.vec[k] + element;
+    }
+
+    pub fn build(&mut self) {
+        let dim = self.shape.len();
+        let mut idx = vec![0; dim];
+        for k in 0..self.vec.len() {
+            let j = (0..dim)
+                .map(|d| (idx[d] + 1) * self.strides[d])
+                .sum::<usize>();
+            self.sum[j] = self.vec[k];
+            for d in (0..dim).rev() {
+                idx[d] += 1;
+                if idx[d] < self.shape[d] {
+                    break;
+                }
+                idx[d] = 0;
+            }
+        }
+        for d in 0..dim {
+            let (stride, size) = (self.strides[d], self.shape[d] + 1);
+            for j in 0..self.sum.len() {
+                if j / stride % size > 0 {
+                    self.sum[j] = self.sum[j] + self.sum[j - stride];
+                }
+            }
+        }
+    }
+
+    /// Sum over the box `[lo[0], hi[0]) x ... x [lo[n - 1], hi[n - 1])`. O(2^n).
+    pub fn query(&self, lo: &[usize], hi: &[usize]) -> T {
+        let dim = self.shape.len();
+        assert!(lo.len() == dim && hi.len() == dim);
+        for d in 0..dim {
+            assert!(lo[d] <= hi[d] && hi[d] <= self.shape[d]);
+        }
+        let mut res = T::zero();
+        for mask in 0..1usize << dim {
+            let j = (0..dim)
+                .map(|d| if mask >> d & 1 == 1 { lo[d] } else { hi[d] } * self.strides[d])
+                .sum::<usize>();
+            if mask.count_ones() % 2 == 0 {
+                res = res + self.sum[j];
+            } else {
+                res = res - self.sum[j];
+            }
+        }
+        res
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PrefixSum2D;
@@ -172,4 +269,64 @@ mod tests {
         assert_eq!(pre.query(4, 5), 5);
         assert_eq!(pre.query(1, 4), 9);
     }
+
+    use super::PrefixSumND;
+    #[test]
+    fn test_prefix_sum_nd() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        let shape = [3, 4, 2];
+        let vec = (0..24)
+            .map(|_| rng.gen_range(-10..10))
+            .collect::<Vec<i64>>();
+        let at = |i: usize, j: usize, k: usize| vec[(i * 4 + j) * 2 + k];
+        let p1 = PrefixSumND::from_vec(&shape, vec.clone());
+        let mut p2 = PrefixSumND::new(&shape);
+        for i in 0..3 {
+            for j in 0..4 {
+                for k in 0..2 {
+                    p2.add(&[i, j, k], at(i, j, k));
+                }
+            }
+        }
+        p2.build();
+
+        for _ in 0..100 {
+            let lo = shape.map(|s| rng.gen_range(0..=s));
+            let hi = [
+                rng.gen_range(lo[0]..=shape[0]),
+                rng.gen_range(lo[1]..=shape[1]),
+                rng.gen_range(lo[2]..=shape[2]),
+            ];
+            let mut expected = 0;
+            for i in lo[0]..hi[0] {
+                for j in lo[1]..hi[1] {
+                    for k in lo[2]..hi[2] {
+                        expected += at(i, j, k);
+                    }
+                }
+            }
+            assert_eq!(p1.query(&lo, &hi), expected);
+            assert_eq!(p2.query(&lo, &hi), expected);
+        }
+
+        let p = PrefixSumND::from_vec(&[5], vec![1, 2, 3, 4, 5]);
+        assert_eq!(p.query(&[1], &[4]), 9);
+        let p = PrefixSumND::from_vec(&[], vec![7]);
+        assert_eq!(p.query(&[], &[]), 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prefix_sum_nd_out_of_shape() {
+        let p = PrefixSumND::from_vec(&[2, 2], vec![1, 2, 3, 4]);
+        p.query(&[0, 0], &[1, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prefix_sum_nd_reversed_box() {
+        let p = PrefixSumND::from_vec(&[2, 2], vec![1, 2, 3, 4]);
+        p.query(&[2, 0], &[1, 2]);
+    }
 }
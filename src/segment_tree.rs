@@ -0,0 +1,603 @@
+use crate::util::to_range;
+use cargo_snippet::snippet;
+
+#[snippet(name = "SegmentTree", include = "to_range")]
+pub trait Monoid {
+    type S: Clone;
+    fn identity() -> Self::S;
+    fn op(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+#[snippet("SegmentTree")]
+pub struct SegmentTree<M: Monoid> {
+    len: usize,
+    size: usize,
+    tree: Vec<M::S>,
+}
+
+#[snippet("SegmentTree")]
+impl<M: Monoid> From<Vec<M::S>> for SegmentTree<M> {
+    fn from(s: Vec<M::S>) -> Self {
+        let len = s.len();
+        let size = len.next_power_of_two();
+        let mut tree = vec![M::identity(); 2 * size];
+        for (i, x) in s.into_iter().enumerate() {
+            tree[size + i] = x;
+        }
+        let mut slf = Self { len, size, tree };
+        for i in (1..size).rev() {
+            slf.pull(i);
+        }
+        slf
+    }
+}
+
+#[snippet("SegmentTree")]
+impl<M: Monoid> SegmentTree<M> {
+    pub fn new(len: usize) -> Self {
+        Self::from(vec![M::identity(); len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pull(&mut self, i: usize) {
+        self.tree[i] = M::op(&self.tree[2 * i], &self.tree[2 * i + 1]);
+    }
+
+    pub fn get(&self, p: usize) -> M::S {
+        assert!(p < self.len);
+        self.tree[self.size + p].clone()
+    }
+
+    pub fn set(&mut self, p: usize, x: M::S) {
+        assert!(p < self.len);
+        let mut i = self.size + p;
+        self.tree[i] = x;
+        while i > 1 {
+            i >>= 1;
+            self.pull(i);
+        }
+    }
+
+    /// Replaces `a[p]` with `op(a[p], x)`.
+    pub fn update(&mut self, p: usize, x: M::S) {
+        let y = M::op(&self.get(p), &x);
+        self.set(p, y);
+    }
+
+    pub fn fold(&self, range: impl std::ops::RangeBounds<usize>) -> M::S {
+        let (l, r) = to_range(range, self.len);
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        let (mut sl, mut sr) = (M::identity(), M::identity());
+        while l < r {
+            if l & 1 == 1 {
+                sl = M::op(&sl, &self.tree[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                sr = M::op(&self.tree[r], &sr);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        M::op(&sl, &sr)
+    }
+
+    /// Largest `r` such that `f(fold(l..r))` holds, for `f` monotone with `f(identity)`.
+    pub fn max_right(&self, l: usize, f: impl Fn(&M::S) -> bool) -> usize {
+        assert!(l <= self.len);
+        assert!(f(&M::identity()));
+        if l == self.len {
+            return self.len;
+        }
+        let mut l = l + self.size;
+        let mut acc = M::identity();
+        loop {
+            while l & 1 == 0 {
+                l >>= 1;
+            }
+            if !f(&M::op(&acc, &self.tree[l])) {
+                while l < self.size {
+                    l *= 2;
+                    let next = M::op(&acc, &self.tree[l]);
+                    if f(&next) {
+                        acc = next;
+                        l += 1;
+                    }
+                }
+                return l - self.size;
+            }
+            acc = M::op(&acc, &self.tree[l]);
+            l += 1;
+            if l & l.wrapping_neg() == l {
+                return self.len;
+            }
+        }
+    }
+
+    /// Smallest `l` such that `f(fold(l..r))` holds, for `f` monotone with `f(identity)`.
+    pub fn min_left(&self, r: usize, f: impl Fn(&M::S) -> bool) -> usize {
+        assert!(r <= self.len);
+        assert!(f(&M::identity()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        let mut acc = M::identity();
+        loop {
+            r -= 1;
+            while r > 1 && r & 1 == 1 {
+                r >>= 1;
+            }
+            if !f(&M::op(&self.tree[r], &acc)) {
+                while r < self.size {
+                    r = 2 * r + 1;
+                    let next = M::op(&self.tree[r], &acc);
+                    if f(&next) {
+                        acc = next;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            acc = M::op(&self.tree[r], &acc);
+            if r & r.wrapping_neg() == r {
+                return 0;
+            }
+        }
+    }
+}
+
+/// Action of the monoid `F` on the values of `M`, distributing over `M::op`.
+#[snippet(name = "LazySegTree", include = "SegmentTree")]
+pub trait MapMonoid {
+    type M: Monoid;
+    /// Lazy actions, where `F::op(f, g)` applies `f` first and then `g`.
+    type F: Monoid;
+    fn mapping(f: &<Self::F as Monoid>::S, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S;
+}
+
+#[snippet("LazySegTree")]
+pub struct LazySegTree<A: MapMonoid> {
+    len: usize,
+    size: usize,
+    log: u32,
+    tree: Vec<<A::M as Monoid>::S>,
+    lazy: Vec<<A::F as Monoid>::S>,
+}
+
+#[snippet("LazySegTree")]
+impl<A: MapMonoid> From<Vec<<A::M as Monoid>::S>> for LazySegTree<A> {
+    fn from(s: Vec<<A::M as Monoid>::S>) -> Self {
+        let len = s.len();
+        let size = len.next_power_of_two();
+        let mut tree = vec![A::M::identity(); 2 * size];
+        for (i, x) in s.into_iter().enumerate() {
+            tree[size + i] = x;
+        }
+        let mut slf = Self {
+            len,
+            size,
+            log: size.trailing_zeros(),
+            tree,
+            lazy: vec![A::F::identity(); size],
+        };
+        for i in (1..size).rev() {
+            slf.pull(i);
+        }
+        slf
+    }
+}
+
+#[snippet("LazySegTree")]
+impl<A: MapMonoid> LazySegTree<A> {
+    pub fn new(len: usize) -> Self {
+        Self::from(vec![A::M::identity(); len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pull(&mut self, i: usize) {
+        self.tree[i] = A::M::op(&self.tree[2 * i], &self.tree[2 * i + 1]);
+    }
+
+    fn apply_node(&mut self, i: usize, f: &<A::F as Monoid>::S) {
+        self.tree[i] = A::mapping(f, &self.tree[i]);
+        if i < self.size {
+            self.lazy[i] = A::F::op(&self.lazy[i], f);
+        }
+    }
+
+    fn push(&mut self, i: usize) {
+        let f = std::mem::replace(&mut self.lazy[i], A::F::identity());
+        self.apply_node(2 * i, &f);
+        self.apply_node(2 * i + 1, &f);
+    }
+
+    /// Pushes the lazy actions on the path from the root down to leaf `i`.
+    fn push_path(&mut self, i: usize) {
+        for k in (1..=self.log).rev() {
+            self.push(i >> k);
+        }
+    }
+
+    pub fn get(&mut self, p: usize) -> <A::M as Monoid>::S {
+        assert!(p < self.len);
+        self.push_path(p + self.size);
+        self.tree[p + self.size].clone()
+    }
+
+    pub fn set(&mut self, p: usize, x: <A::M as Monoid>::S) {
+        assert!(p < self.len);
+        let p = p + self.size;
+        self.push_path(p);
+        self.tree[p] = x;
+        for k in 1..=self.log {
+            self.pull(p >> k);
+        }
+    }
+
+    pub fn fold(&mut self, range: impl std::ops::RangeBounds<usize>) -> <A::M as Monoid>::S {
+        let (l, r) = to_range(range, self.len);
+        if l == r {
+            return A::M::identity();
+        }
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        for k in (1..=self.log).rev() {
+            if (l >> k) << k != l {
+                self.push(l >> k);
+            }
+            if (r >> k) << k != r {
+                self.push((r - 1) >> k);
+            }
+        }
+        let (mut sl, mut sr) = (A::M::identity(), A::M::identity());
+        while l < r {
+            if l & 1 == 1 {
+                sl = A::M::op(&sl, &self.tree[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                sr = A::M::op(&self.tree[r], &sr);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        A::M::op(&sl, &sr)
+    }
+
+    pub fn apply(&mut self, range: impl std::ops::RangeBounds<usize>, f: <A::F as Monoid>::S) {
+        let (l, r) = to_range(range, self.len);
+        if l == r {
+            return;
+        }
+        let (l, r) = (l + self.size, r + self.size);
+        for k in (1..=self.log).rev() {
+            if (l >> k) << k != l {
+                self.push(l >> k);
+            }
+            if (r >> k) << k != r {
+                self.push((r - 1) >> k);
+            }
+        }
+        let (mut a, mut b) = (l, r);
+        while a < b {
+            if a & 1 == 1 {
+                self.apply_node(a, &f);
+                a += 1;
+            }
+            if b & 1 == 1 {
+                b -= 1;
+                self.apply_node(b, &f);
+            }
+            a >>= 1;
+            b >>= 1;
+        }
+        for k in 1..=self.log {
+            if (l >> k) << k != l {
+                self.pull(l >> k);
+            }
+            if (r >> k) << k != r {
+                self.pull((r - 1) >> k);
+            }
+        }
+    }
+
+    /// Largest `r` such that `g(fold(l..r))` holds, for `g` monotone with `g(identity)`.
+    pub fn max_right(&mut self, l: usize, g: impl Fn(&<A::M as Monoid>::S) -> bool) -> usize {
+        assert!(l <= self.len);
+        assert!(g(&A::M::identity()));
+        if l == self.len {
+            return self.len;
+        }
+        let mut l = l + self.size;
+        self.push_path(l);
+        let mut acc = A::M::identity();
+        loop {
+            while l & 1 == 0 {
+                l >>= 1;
+            }
+            if !g(&A::M::op(&acc, &self.tree[l])) {
+                while l < self.size {
+                    self.push(l);
+                    l *= 2;
+                    let next = A::M::op(&acc, &self.tree[l]);
+                    if g(&next) {
+                        acc = next;
+                        l += 1;
+                    }
+                }
+                return l - self.size;
+            }
+            acc = A::M::op(&acc, &self.tree[l]);
+            l += 1;
+            if l & l.wrapping_neg() == l {
+                return self.len;
+            }
+        }
+    }
+
+    /// Smallest `l` such that `g(fold(l..r))` holds, for `g` monotone with `g(identity)`.
+    pub fn min_left(&mut self, r: usize, g: impl Fn(&<A::M as Monoid>::S) -> bool) -> usize {
+        assert!(r <= self.len);
+        assert!(g(&A::M::identity()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        self.push_path(r - 1);
+        let mut acc = A::M::identity();
+        loop {
+            r -= 1;
+            while r > 1 && r & 1 == 1 {
+                r >>= 1;
+            }
+            if !g(&A::M::op(&self.tree[r], &acc)) {
+                while r < self.size {
+                    self.push(r);
+                    r = 2 * r + 1;
+                    let next = A::M::op(&self.tree[r], &acc);
+                    if g(&next) {
+                        acc = next;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            acc = A::M::op(&self.tree[r], &acc);
+            if r & r.wrapping_neg() == r {
+                return 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LazySegTree, MapMonoid, Monoid, SegmentTree};
+    use crate::modint::ModInt998244353;
+    use rand::prelude::*;
+
+    struct Min;
+    impl Monoid for Min {
+        type S = i64;
+        fn identity() -> i64 {
+            i64::MAX
+        }
+        fn op(a: &i64, b: &i64) -> i64 {
+            *a.min(b)
+        }
+    }
+
+    struct Sum;
+    impl Monoid for Sum {
+        type S = i64;
+        fn identity() -> i64 {
+            0
+        }
+        fn op(a: &i64, b: &i64) -> i64 {
+            a + b
+        }
+    }
+
+    /// Non-commutative: concatenation of strings.
+    struct Concat;
+    impl Monoid for Concat {
+        type S = String;
+        fn identity() -> String {
+            String::new()
+        }
+        fn op(a: &String, b: &String) -> String {
+            format!("{}{}", a, b)
+        }
+    }
+
+    #[test]
+    fn test_segment_tree() {
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(0..40);
+            let mut vec = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<i64>>();
+            let mut min = SegmentTree::<Min>::from(vec.clone());
+            let mut sum = SegmentTree::<Sum>::new(n);
+            for (i, &x) in vec.iter().enumerate() {
+                sum.set(i, x);
+            }
+            for _ in 0..50 {
+                if n > 0 {
+                    let p = rng.gen_range(0..n);
+                    let x = rng.gen_range(0..100);
+                    if rng.gen() {
+                        vec[p] = x;
+                        min.set(p, x);
+                        sum.set(p, x);
+                    } else {
+                        vec[p] += x;
+                        min.set(p, vec[p]);
+                        sum.update(p, x);
+                    }
+                    assert_eq!(sum.get(p), vec[p]);
+                }
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                assert_eq!(
+                    min.fold(l..r),
+                    vec[l..r].iter().copied().min().unwrap_or(i64::MAX)
+                );
+                assert_eq!(sum.fold(l..r), vec[l..r].iter().sum());
+                assert_eq!(sum.fold(..), vec.iter().sum());
+
+                let limit = rng.gen_range(0..500);
+                let expected = (l..=n)
+                    .rev()
+                    .find(|&r| vec[l..r].iter().sum::<i64>() <= limit)
+                    .unwrap();
+                assert_eq!(sum.max_right(l, |&s| s <= limit), expected);
+                let expected = (0..=r)
+                    .find(|&l| vec[l..r].iter().sum::<i64>() <= limit)
+                    .unwrap();
+                assert_eq!(sum.min_left(r, |&s| s <= limit), expected);
+
+                let expected = (l..=n)
+                    .rev()
+                    .find(|&r| vec[l..r].iter().all(|&x| x >= limit / 5))
+                    .unwrap();
+                assert_eq!(min.max_right(l, |&m| m >= limit / 5), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_tree_non_commutative() {
+        let s = "abcdefg".chars().map(|c| c.to_string()).collect::<Vec<_>>();
+        let mut seg = SegmentTree::<Concat>::from(s);
+        assert_eq!(seg.fold(1..5), "bcde");
+        seg.set(2, "X".to_string());
+        assert_eq!(seg.fold(..), "abXdefg");
+        assert_eq!(seg.max_right(1, |s| s.len() <= 3), 4);
+        assert_eq!(seg.min_left(6, |s| !s.contains('X')), 3);
+    }
+
+    /// Range add on range min.
+    struct AddMin;
+    impl MapMonoid for AddMin {
+        type M = Min;
+        type F = Sum;
+        fn mapping(f: &i64, x: &i64) -> i64 {
+            if *x == i64::MAX {
+                *x
+            } else {
+                x + f
+            }
+        }
+    }
+
+    type Mint = ModInt998244353;
+
+    /// Sum and length of a segment.
+    struct SumLen;
+    impl Monoid for SumLen {
+        type S = (Mint, Mint);
+        fn identity() -> Self::S {
+            (Mint::new(0), Mint::new(0))
+        }
+        fn op(a: &Self::S, b: &Self::S) -> Self::S {
+            (a.0 + b.0, a.1 + b.1)
+        }
+    }
+
+    /// `x -> a x + b`.
+    struct Affine;
+    impl Monoid for Affine {
+        type S = (Mint, Mint);
+        fn identity() -> Self::S {
+            (Mint::new(1), Mint::new(0))
+        }
+        fn op(f: &Self::S, g: &Self::S) -> Self::S {
+            (g.0 * f.0, g.0 * f.1 + g.1)
+        }
+    }
+
+    struct AffineSum;
+    impl MapMonoid for AffineSum {
+        type M = SumLen;
+        type F = Affine;
+        fn mapping(f: &(Mint, Mint), x: &(Mint, Mint)) -> (Mint, Mint) {
+            (f.0 * x.0 + f.1 * x.1, x.1)
+        }
+    }
+
+    #[test]
+    fn test_lazy_seg_tree_add_min() {
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(0..40);
+            let mut vec = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<i64>>();
+            let mut seg = LazySegTree::<AddMin>::from(vec.clone());
+            for _ in 0..50 {
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                match rng.gen_range(0..3) {
+                    0 => {
+                        let x = rng.gen_range(-20..20);
+                        seg.apply(l..r, x);
+                        vec[l..r].iter_mut().for_each(|v| *v += x);
+                    }
+                    1 if n > 0 => {
+                        let (p, x) = (rng.gen_range(0..n), rng.gen_range(-100..100));
+                        seg.set(p, x);
+                        vec[p] = x;
+                        assert_eq!(seg.get(p), x);
+                    }
+                    _ => {}
+                }
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                let min = |l: usize, r: usize| vec[l..r].iter().copied().min().unwrap_or(i64::MAX);
+                assert_eq!(seg.fold(l..r), min(l, r));
+
+                let t = rng.gen_range(-100..100);
+                let expected = (l..=n).rev().find(|&r| min(l, r) >= t).unwrap();
+                assert_eq!(seg.max_right(l, |&m| m >= t), expected);
+                let expected = (0..=r).find(|&l| min(l, r) >= t).unwrap();
+                assert_eq!(seg.min_left(r, |&m| m >= t), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lazy_seg_tree_affine_sum() {
+        let mut rng = thread_rng();
+        let n = 50;
+        let mut vec = (0..n).map(|_| Mint::new(rng.gen())).collect::<Vec<_>>();
+        let mut seg = LazySegTree::<AffineSum>::from(
+            vec.iter().map(|&x| (x, Mint::new(1))).collect::<Vec<_>>(),
+        );
+        for _ in 0..500 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            if rng.gen() {
+                let (a, b) = (Mint::new(rng.gen()), Mint::new(rng.gen()));
+                seg.apply(l..r, (a, b));
+                vec[l..r].iter_mut().for_each(|v| *v = a * *v + b);
+            } else {
+                let expected = vec[l..r].iter().fold(Mint::new(0), |s, &x| s + x);
+                assert_eq!(seg.fold(l..r).0, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::number::Number;
+use std::ops::{Add, AddAssign, Mul};
+
+/// `(min, +)` with `i64::MAX` as infinity.
+/// `Matrix<MinPlus>::pow(k)` gives the shortest walks with exactly `k` edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MinPlus(pub i64);
+
+/// `(max, +)` with `i64::MIN` as negative infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxPlus(pub i64);
+
+/// `(max, min)`, e.g. for bottleneck paths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxMin(pub i64);
+
+/// `(or, and)`, e.g. for reachability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Boolean(pub bool);
+
+impl Number for MinPlus {
+    fn zero() -> Self {
+        MinPlus(i64::MAX)
+    }
+
+    fn one() -> Self {
+        MinPlus(0)
+    }
+}
+
+impl Add for MinPlus {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        MinPlus(self.0.min(rhs.0))
+    }
+}
+
+impl Mul for MinPlus {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        if self.0 == i64::MAX || rhs.0 == i64::MAX {
+            MinPlus(i64::MAX)
+        } else {
+            MinPlus(self.0 + rhs.0)
+        }
+    }
+}
+
+impl Number for MaxPlus {
+    fn zero() -> Self {
+        MaxPlus(i64::MIN)
+    }
+
+    fn one() -> Self {
+        MaxPlus(0)
+    }
+}
+
+impl Add for MaxPlus {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        MaxPlus(self.0.max(rhs.0))
+    }
+}
+
+impl Mul for MaxPlus {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        if self.0 == i64::MIN || rhs.0 == i64::MIN {
+            MaxPlus(i64::MIN)
+        } else {
+            MaxPlus(self.0 + rhs.0)
+        }
+    }
+}
+
+impl Number for MaxMin {
+    fn zero() -> Self {
+        MaxMin(i64::MIN)
+    }
+
+    fn one() -> Self {
+        MaxMin(i64::MAX)
+    }
+}
+
+impl Add for MaxMin {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        MaxMin(self.0.max(rhs.0))
+    }
+}
+
+impl Mul for MaxMin {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        MaxMin(self.0.min(rhs.0))
+    }
+}
+
+impl Number for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+
+    fn one() -> Self {
+        Boolean(true)
+    }
+}
+
+impl Add for Boolean {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Boolean(self.0 || rhs.0)
+    }
+}
+
+impl Mul for Boolean {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Boolean(self.0 && rhs.0)
+    }
+}
+
+impl AddAssign for MinPlus {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign for MaxPlus {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign for MaxMin {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign for Boolean {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boolean, MaxMin, MaxPlus, MinPlus};
+    use crate::matrix::Matrix;
+    use crate::number::Number;
+    use rand::prelude::*;
+    use std::ops::{Add, Mul};
+
+    /// `k`-th power of `adj` by stepping a DP from every vertex.
+    fn walks<T>(adj: &[Vec<T>], k: usize) -> Vec<Vec<T>>
+    where
+        T: Number + Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        let n = adj.len();
+        (0..n)
+            .map(|s| {
+                let mut dp = vec![T::zero(); n];
+                dp[s] = T::one();
+                for _ in 0..k {
+                    let mut next = vec![T::zero(); n];
+                    for u in 0..n {
+                        for v in 0..n {
+                            next[v] = next[v] + dp[u] * adj[u][v];
+                        }
+                    }
+                    dp = next;
+                }
+                dp
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_semirings() {
+        let mut rng = thread_rng();
+        let n = 6;
+        for k in 0..8 {
+            let w = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| {
+                            if rng.gen_bool(0.4) {
+                                Some(rng.gen_range(-10..10))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            let adj = w
+                .iter()
+                .map(|r| {
+                    r.iter()
+                        .map(|x| MinPlus(x.unwrap_or(i64::MAX)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let expected = walks(&adj, k);
+            assert_eq!(Matrix::new(adj).pow(k as u64), Matrix::new(expected));
+
+            let adj = w
+                .iter()
+                .map(|r| {
+                    r.iter()
+                        .map(|x| MaxPlus(x.unwrap_or(i64::MIN)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let expected = walks(&adj, k);
+            assert_eq!(Matrix::new(adj).pow(k as u64), Matrix::new(expected));
+
+            let adj = w
+                .iter()
+                .map(|r| {
+                    r.iter()
+                        .map(|x| MaxMin(x.unwrap_or(i64::MIN)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let expected = walks(&adj, k);
+            assert_eq!(Matrix::new(adj).pow(k as u64), Matrix::new(expected));
+
+            let adj = w
+                .iter()
+                .map(|r| r.iter().map(|x| Boolean(x.is_some())).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let expected = walks(&adj, k);
+            assert_eq!(Matrix::new(adj).pow(k as u64), Matrix::new(expected));
+        }
+    }
+
+    #[test]
+    fn test_shortest_walk() {
+        let inf = MinPlus(i64::MAX);
+        let adj = Matrix::new(vec![
+            vec![inf, MinPlus(1), MinPlus(5)],
+            vec![inf, inf, MinPlus(1)],
+            vec![MinPlus(2), inf, inf],
+        ]);
+        let a = adj.pow(2);
+        assert_eq!(a[(0, 2)], MinPlus(2));
+        assert_eq!(a[(0, 0)], MinPlus(7));
+        assert_eq!(a[(1, 1)], inf);
+    }
+}
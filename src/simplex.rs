@@ -0,0 +1,205 @@
+use crate::matrix::Matrix;
+
+#[derive(Clone, Debug)]
+pub enum LpResult {
+    Infeasible,
+    Unbounded,
+    /// Optimal value and a primal solution attaining it.
+    Optimal(f64, Vec<f64>),
+}
+
+/// Maximizes `c x` subject to `a x <= b` and `x >= 0` by the two-phase simplex method
+/// with Bland's rule, so degenerate problems do not cycle.
+pub fn simplex(a: &Matrix<f64>, b: &[f64], c: &[f64]) -> LpResult {
+    const EPS: f64 = 1e-9;
+    // Label of the artificial variable used in the first phase.
+    const ART: usize = usize::MAX;
+    let (m, n) = a.shape();
+    assert_eq!(b.len(), m);
+    assert_eq!(c.len(), n);
+
+    // Rows `0..m` are the constraints, row `m` the objective and row `m + 1` the
+    // auxiliary objective. Column `n` is the artificial variable, column `n + 1` the rhs.
+    let mut d = Matrix::zeros(m + 2, n + 2);
+    let mut basic = (n..n + m).collect::<Vec<_>>();
+    let mut non_basic = (0..n).chain(Some(ART)).collect::<Vec<_>>();
+    for i in 0..m {
+        d.row_mut(i)[..n].copy_from_slice(a.row(i));
+        d[(i, n)] = -1.;
+        d[(i, n + 1)] = b[i];
+    }
+    for j in 0..n {
+        d[(m, j)] = -c[j];
+    }
+    d[(m + 1, n)] = 1.;
+
+    let pivot = |d: &mut Matrix<f64>, basic: &mut [usize], non_basic: &mut [usize], r, s| {
+        let inv = 1. / d[(r, s)];
+        let pr = d.row(r).to_vec();
+        for i in 0..m + 2 {
+            if i != r && d[(i, s)].abs() > EPS {
+                let f = d[(i, s)] * inv;
+                for (x, &p) in d.row_mut(i).iter_mut().zip(&pr) {
+                    *x -= p * f;
+                }
+                d[(i, s)] = -f;
+            }
+        }
+        for x in d.row_mut(r).iter_mut() {
+            *x *= inv;
+        }
+        d[(r, s)] = inv;
+        std::mem::swap(&mut basic[r], &mut non_basic[s]);
+    };
+
+    // Runs the simplex on objective row `x`. Returns `false` if it is unbounded.
+    let run = |d: &mut Matrix<f64>, basic: &mut Vec<usize>, non_basic: &mut Vec<usize>, x| loop {
+        let s = (0..n + 1)
+            .filter(|&j| (x == m + 1 || non_basic[j] != ART) && d[(x, j)] < -EPS)
+            .min_by_key(|&j| non_basic[j]);
+        let s = match s {
+            Some(s) => s,
+            None => return true,
+        };
+        let mut r: Option<usize> = None;
+        for i in 0..m {
+            if d[(i, s)] <= EPS {
+                continue;
+            }
+            r = match r {
+                Some(r) => {
+                    let (ri, rr) = (d[(i, n + 1)] / d[(i, s)], d[(r, n + 1)] / d[(r, s)]);
+                    if ri < rr - EPS || (ri < rr + EPS && basic[i] < basic[r]) {
+                        Some(i)
+                    } else {
+                        Some(r)
+                    }
+                }
+                None => Some(i),
+            };
+        }
+        match r {
+            Some(r) => pivot(d, basic, non_basic, r, s),
+            None => return false,
+        }
+    };
+
+    if let Some(r) = (0..m).min_by(|&i, &j| d[(i, n + 1)].partial_cmp(&d[(j, n + 1)]).unwrap()) {
+        if d[(r, n + 1)] < -EPS {
+            pivot(&mut d, &mut basic, &mut non_basic, r, n);
+            if !run(&mut d, &mut basic, &mut non_basic, m + 1) || d[(m + 1, n + 1)] < -EPS {
+                return LpResult::Infeasible;
+            }
+            if let Some(i) = (0..m).find(|&i| basic[i] == ART) {
+                let s = (0..n + 1)
+                    .filter(|&j| non_basic[j] != ART)
+                    .max_by(|&j, &k| d[(i, j)].abs().partial_cmp(&d[(i, k)].abs()).unwrap())
+                    .unwrap();
+                if d[(i, s)].abs() > EPS {
+                    pivot(&mut d, &mut basic, &mut non_basic, i, s);
+                }
+            }
+        }
+    }
+    if !run(&mut d, &mut basic, &mut non_basic, m) {
+        return LpResult::Unbounded;
+    }
+    let mut x = vec![0.; n];
+    for i in 0..m {
+        if basic[i] < n {
+            x[basic[i]] = d[(i, n + 1)];
+        }
+    }
+    LpResult::Optimal(d[(m, n + 1)], x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simplex, LpResult};
+    use crate::matrix::Matrix;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_simplex() {
+        // max 3x + 2y s.t. x + y <= 4, x + 3y <= 6, x <= 3
+        let a = Matrix::new(vec![vec![1., 1.], vec![1., 3.], vec![1., 0.]]);
+        match simplex(&a, &[4., 6., 3.], &[3., 2.]) {
+            LpResult::Optimal(v, x) => {
+                assert!((v - 11.).abs() < 1e-9);
+                assert!((x[0] - 3.).abs() < 1e-9 && (x[1] - 1.).abs() < 1e-9);
+            }
+            r => panic!("{:?}", r),
+        }
+
+        // x >= 2 and x <= 1
+        let a = Matrix::new(vec![vec![-1.], vec![1.]]);
+        assert!(matches!(
+            simplex(&a, &[-2., 1.], &[1.]),
+            LpResult::Infeasible
+        ));
+
+        // max x + y s.t. x - y <= 1
+        let a = Matrix::new(vec![vec![1., -1.]]);
+        assert!(matches!(simplex(&a, &[1.], &[1., 1.]), LpResult::Unbounded));
+
+        // x >= 1, y >= 1, x + y <= 3: the origin is infeasible
+        let a = Matrix::new(vec![vec![-1., 0.], vec![0., -1.], vec![1., 1.]]);
+        match simplex(&a, &[-1., -1., 3.], &[-1., -2.]) {
+            LpResult::Optimal(v, x) => {
+                assert!((v + 3.).abs() < 1e-9);
+                assert!((x[0] - 1.).abs() < 1e-9 && (x[1] - 1.).abs() < 1e-9);
+            }
+            r => panic!("{:?}", r),
+        }
+
+        // Beale's example, which cycles under the largest coefficient rule.
+        let a = Matrix::new(vec![
+            vec![0.25, -8., -1., 9.],
+            vec![0.5, -12., -0.5, 3.],
+            vec![0., 0., 1., 0.],
+        ]);
+        match simplex(&a, &[0., 0., 1.], &[0.75, -20., 0.5, -6.]) {
+            LpResult::Optimal(v, _) => assert!((v - 1.25).abs() < 1e-9),
+            r => panic!("{:?}", r),
+        }
+
+        let a = Matrix::new(vec![]);
+        assert!(matches!(simplex(&a, &[], &[]), LpResult::Optimal(v, _) if v == 0.));
+    }
+
+    #[test]
+    fn test_simplex_duality() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let (m, n) = (rng.gen_range(1..6), rng.gen_range(1..6));
+            let a = Matrix::from_fn(m, n, |_, _| rng.gen_range(-5..=5) as f64);
+            let b = (0..m)
+                .map(|_| rng.gen_range(-5..=10) as f64)
+                .collect::<Vec<_>>();
+            let c = (0..n)
+                .map(|_| rng.gen_range(-5..=5) as f64)
+                .collect::<Vec<_>>();
+
+            // min b y s.t. a^T y >= c, y >= 0
+            let at = Matrix::from_fn(n, m, |i, j| -a[(j, i)]);
+            let nb = b.iter().map(|x| -x).collect::<Vec<_>>();
+            let nc = c.iter().map(|x| -x).collect::<Vec<_>>();
+            match (simplex(&a, &b, &c), simplex(&at, &nc, &nb)) {
+                (LpResult::Optimal(p, x), LpResult::Optimal(d, _)) => {
+                    assert!((p + d).abs() < 1e-6);
+                    assert!(x.iter().all(|&x| x > -1e-9));
+                    for i in 0..m {
+                        let lhs = (0..n).map(|j| a[(i, j)] * x[j]).sum::<f64>();
+                        assert!(lhs < b[i] + 1e-6);
+                    }
+                    let value = (0..n).map(|j| c[j] * x[j]).sum::<f64>();
+                    assert!((value - p).abs() < 1e-6);
+                }
+                (LpResult::Unbounded, d) => assert!(matches!(d, LpResult::Infeasible)),
+                (p, LpResult::Unbounded) => assert!(matches!(p, LpResult::Infeasible)),
+                (LpResult::Infeasible, _) => {}
+                (p, d) => panic!("{:?} {:?}", p, d),
+            }
+        }
+    }
+}
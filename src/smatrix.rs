@@ -0,0 +1,220 @@
+use crate::matrix::Matrix;
+use crate::number::{Number, Semiring};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+
+/// `N x M` matrix stored inline, so products and powers do not allocate.
+/// Dimensions of products are checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SMatrix<T, const N: usize, const M: usize> {
+    v: [[T; M]; N],
+}
+
+impl<T, const N: usize, const M: usize> SMatrix<T, N, M> {
+    pub fn new(v: [[T; M]; N]) -> Self {
+        Self { v }
+    }
+
+    pub fn zeros() -> Self
+    where
+        T: Number + Copy,
+    {
+        Self {
+            v: [[T::zero(); M]; N],
+        }
+    }
+
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self {
+            v: std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))),
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &[T; M] {
+        &self.v[i]
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, M, N>
+    where
+        T: Copy,
+    {
+        SMatrix::from_fn(|i, j| self.v[j][i])
+    }
+
+    pub fn mul_vec(&self, x: [T; M]) -> [T; N]
+    where
+        T: Semiring,
+    {
+        let mut res = [T::zero(); N];
+        for i in 0..N {
+            for j in 0..M {
+                res[i] += self.v[i][j] * x[j];
+            }
+        }
+        res
+    }
+}
+
+impl<T, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> Self
+    where
+        T: Number + Copy,
+    {
+        let mut res = Self::zeros();
+        for i in 0..N {
+            res.v[i][i] = T::one();
+        }
+        res
+    }
+
+    pub fn pow(&self, mut n: u64) -> Self
+    where
+        T: Semiring,
+    {
+        let mut res = Self::identity();
+        let mut x = *self;
+        while n > 0 {
+            if n & 1 == 1 {
+                res *= x;
+            }
+            n >>= 1;
+            if n > 0 {
+                x *= x;
+            }
+        }
+        res
+    }
+}
+
+impl<T: Clone, const N: usize, const M: usize> From<SMatrix<T, N, M>> for Matrix<T> {
+    fn from(a: SMatrix<T, N, M>) -> Self {
+        Matrix::from_fn(N, M, |i, j| a.v[i][j].clone())
+    }
+}
+
+impl<T, const N: usize, const M: usize> Index<(usize, usize)> for SMatrix<T, N, M> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.v[i][j]
+    }
+}
+
+impl<T, const N: usize, const M: usize> IndexMut<(usize, usize)> for SMatrix<T, N, M> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.v[i][j]
+    }
+}
+
+impl<T, const N: usize, const M: usize> AddAssign for SMatrix<T, N, M>
+where
+    T: AddAssign + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        for i in 0..N {
+            for j in 0..M {
+                self.v[i][j] += rhs.v[i][j];
+            }
+        }
+    }
+}
+
+impl<T, const N: usize, const M: usize> SubAssign for SMatrix<T, N, M>
+where
+    T: SubAssign + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        for i in 0..N {
+            for j in 0..M {
+                self.v[i][j] -= rhs.v[i][j];
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> MulAssign for SMatrix<T, N, N>
+where
+    T: Semiring,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T, const N: usize, const M: usize> Add for SMatrix<T, N, M>
+where
+    T: AddAssign + Copy,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut res = self;
+        res += rhs;
+        res
+    }
+}
+
+impl<T, const N: usize, const M: usize> Sub for SMatrix<T, N, M>
+where
+    T: SubAssign + Copy,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut res = self;
+        res -= rhs;
+        res
+    }
+}
+
+impl<T, const N: usize, const M: usize, const K: usize> Mul<SMatrix<T, M, K>> for SMatrix<T, N, M>
+where
+    T: Semiring,
+{
+    type Output = SMatrix<T, N, K>;
+    fn mul(self, rhs: SMatrix<T, M, K>) -> Self::Output {
+        let mut res = SMatrix::zeros();
+        for i in 0..N {
+            for k in 0..M {
+                let a = self.v[i][k];
+                for j in 0..K {
+                    res.v[i][j] += a * rhs.v[k][j];
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SMatrix;
+    use crate::matrix::Matrix;
+    use crate::modint::ModInt998244353;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_smatrix_mul() {
+        let a = SMatrix::new([[1, 2], [0, 3]]);
+        let b = SMatrix::new([[1, 0, 2], [2, 1, 3]]);
+        assert_eq!(a * b, SMatrix::new([[5, 2, 8], [6, 3, 9]]));
+        assert_eq!(b.transpose() * a.transpose(), (a * b).transpose());
+        assert_eq!(a.mul_vec([1, -1]), [-1, -3]);
+
+        let mut rng = thread_rng();
+        let a = SMatrix::<i64, 3, 4>::from_fn(|_, _| rng.gen_range(-10..10));
+        let b = SMatrix::<i64, 4, 2>::from_fn(|_, _| rng.gen_range(-10..10));
+        assert_eq!(Matrix::from(a * b), Matrix::from(a) * Matrix::from(b));
+        assert_eq!(a + a - a, a);
+    }
+
+    #[test]
+    fn test_smatrix_pow() {
+        let fib = SMatrix::new([[1, 1], [1, 0]]);
+        assert_eq!(fib.pow(0), SMatrix::identity());
+        assert_eq!(fib.pow(10), SMatrix::new([[89, 55], [55, 34]]));
+
+        type Mint = ModInt998244353;
+        let mut rng = thread_rng();
+        let a = SMatrix::<Mint, 8, 8>::from_fn(|_, _| Mint::new(rng.gen()));
+        for n in [0, 1, 2, 7, 1_000_000_007] {
+            assert_eq!(Matrix::from(a.pow(n)), Matrix::from(a).pow(n));
+        }
+    }
+}
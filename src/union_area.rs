@@ -0,0 +1,187 @@
+use crate::circle::Circle;
+use crate::complex::{Complex, EPS};
+use cargo_snippet::snippet;
+
+/// Area of the union of circles by integrating `(x dy - y dx) / 2` along uncovered arcs.
+/// O(n^2 log n).
+#[snippet(name = "circle_union_area", include = "Circle")]
+pub fn circle_union_area(cs: &[Circle]) -> f64 {
+    use std::f64::consts::PI;
+    let arc = |c: &Circle, s: f64, t: f64| {
+        (c.r * c.r * (t - s) + c.c.x * c.r * (t.sin() - s.sin())
+            - c.c.y * c.r * (t.cos() - s.cos()))
+            / 2.
+    };
+
+    let mut res = 0.;
+    'outer: for (i, ci) in cs.iter().enumerate() {
+        if ci.r < EPS {
+            continue;
+        }
+        let mut segs = vec![];
+        for (j, cj) in cs.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let d = (cj.c - ci.c).abs();
+            if d < EPS && (ci.r - cj.r).abs() < EPS {
+                if j < i {
+                    continue 'outer;
+                }
+                continue;
+            }
+            if d + ci.r <= cj.r + EPS {
+                continue 'outer;
+            }
+            if d >= ci.r + cj.r - EPS || d + cj.r <= ci.r + EPS {
+                continue;
+            }
+            let a = (cj.c - ci.c).arg();
+            let b = ((ci.r * ci.r + d * d - cj.r * cj.r) / (2. * ci.r * d))
+                .clamp(-1., 1.)
+                .acos();
+            let (l, r) = (a - b, a + b);
+            if l < -PI {
+                segs.push((l + 2. * PI, PI));
+                segs.push((-PI, r));
+            } else if r > PI {
+                segs.push((l, PI));
+                segs.push((-PI, r - 2. * PI));
+            } else {
+                segs.push((l, r));
+            }
+        }
+        segs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut cur = -PI;
+        for (l, r) in segs {
+            if l > cur {
+                res += arc(ci, cur, l);
+            }
+            cur = cur.max(r);
+        }
+        res += arc(ci, cur, PI);
+    }
+    res
+}
+
+/// Area of the union of simple polygons given in counter-clockwise order.
+/// O(N^2 log N) for N vertices in total.
+#[snippet(name = "polygon_union_area", include = "Complex")]
+pub fn polygon_union_area(polys: &[Vec<Complex>]) -> f64 {
+    let sgn = |x: f64| -> i32 {
+        if x > EPS {
+            1
+        } else if x < -EPS {
+            -1
+        } else {
+            0
+        }
+    };
+    let ratio = |a: Complex, b: Complex| if sgn(b.x) != 0 { a.x / b.x } else { a.y / b.y };
+
+    let mut res = 0.;
+    for (i, poly) in polys.iter().enumerate() {
+        for v in 0..poly.len() {
+            let (a, b) = (poly[v], poly[(v + 1) % poly.len()]);
+            let mut segs = vec![(0., 0), (1., 0)];
+            for (j, other) in polys.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                for u in 0..other.len() {
+                    let (c, d) = (other[u], other[(u + 1) % other.len()]);
+                    let sc = sgn((b - a).cross(c - a));
+                    let sd = sgn((b - a).cross(d - a));
+                    if sc != sd {
+                        let sa = (d - c).cross(a - c);
+                        let sb = (d - c).cross(b - c);
+                        if sc.min(sd) < 0 {
+                            segs.push((sa / (sa - sb), (sc - sd).signum()));
+                        }
+                    } else if sc == 0 && sd == 0 && j < i && sgn((b - a).dot(d - c)) > 0 {
+                        segs.push((ratio(c - a, b - a), 1));
+                        segs.push((ratio(d - a, b - a), -1));
+                    }
+                }
+            }
+            segs.sort_by(|x, y| x.partial_cmp(y).unwrap());
+            let mut sum = 0.;
+            let mut cnt = segs[0].1;
+            for k in 1..segs.len() {
+                if cnt == 0 {
+                    sum += segs[k].0.clamp(0., 1.) - segs[k - 1].0.clamp(0., 1.);
+                }
+                cnt += segs[k].1;
+            }
+            res += a.cross(b) * sum;
+        }
+    }
+    res / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{circle_union_area, polygon_union_area};
+    use crate::circle::Circle;
+    use crate::complex::Complex;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_circle_union_area() {
+        let c = |x: f64, y: f64, r: f64| Circle::new(Complex::new(x, y), r);
+        assert!((circle_union_area(&[c(1., 2., 1.)]) - PI).abs() < 1e-9);
+        assert!((circle_union_area(&[c(1., 2., 1.), c(1., 2., 1.)]) - PI).abs() < 1e-9);
+        assert!((circle_union_area(&[c(0., 0., 2.), c(0.5, 0., 1.)]) - 4. * PI).abs() < 1e-9);
+        assert!((circle_union_area(&[c(0., 0., 1.), c(5., 0., 1.)]) - 2. * PI).abs() < 1e-9);
+        let lens = 2. * PI / 3. - 3f64.sqrt() / 2.;
+        let area = circle_union_area(&[c(0., 0., 1.), c(1., 0., 1.)]);
+        assert!((area - (2. * PI - lens)).abs() < 1e-9);
+
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let mut gen = || {
+                c(
+                    rng.gen_range(-5.0..5.0),
+                    rng.gen_range(-5.0..5.0),
+                    rng.gen_range(0.5..5.0),
+                )
+            };
+            let (a, b) = (gen(), gen());
+            let expected = PI * (a.r * a.r + b.r * b.r) - a.intersection_area(&b);
+            assert!((circle_union_area(&[a, b]) - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_polygon_union_area() {
+        let rect = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            vec![
+                Complex::new(x1, y1),
+                Complex::new(x2, y1),
+                Complex::new(x2, y2),
+                Complex::new(x1, y2),
+            ]
+        };
+        assert!((polygon_union_area(&[rect(0., 0., 2., 2.)]) - 4.).abs() < 1e-9);
+        let polys = vec![rect(0., 0., 2., 2.), rect(1., 1., 3., 3.)];
+        assert!((polygon_union_area(&polys) - 7.).abs() < 1e-9);
+        let polys = vec![rect(0., 0., 2., 2.), rect(0., 0., 2., 2.)];
+        assert!((polygon_union_area(&polys) - 4.).abs() < 1e-9);
+        let polys = vec![rect(0., 0., 1., 1.), rect(1., 0., 2., 1.)];
+        assert!((polygon_union_area(&polys) - 2.).abs() < 1e-9);
+        let polys = vec![
+            rect(0., 0., 3., 3.),
+            rect(1., 1., 2., 2.),
+            rect(5., 5., 6., 7.),
+        ];
+        assert!((polygon_union_area(&polys) - 11.).abs() < 1e-9);
+        let tri = vec![
+            Complex::new(0., 0.),
+            Complex::new(4., 0.),
+            Complex::new(0., 4.),
+        ];
+        let polys = vec![tri, rect(1., -1., 3., 1.)];
+        assert!((polygon_union_area(&polys) - 10.).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,7 @@
 use cargo_snippet::snippet;
 
 #[snippet(name = "next_permutation")]
-fn next_permutation<T: Ord>(v: &mut [T]) -> Option<usize> {
+pub fn next_permutation<T: Ord>(v: &mut [T]) -> Option<usize> {
     let mut tail_dec: usize = 1;
     let n = v.len();
     while tail_dec < n {
@@ -35,3 +35,21 @@ fn next_permutation<T: Ord>(v: &mut [T]) -> Option<usize> {
         None
     }
 }
+
+/// Converts `range` into a half-open `(l, r)` within `[0, len]`.
+#[snippet("to_range")]
+pub fn to_range(range: impl std::ops::RangeBounds<usize>, len: usize) -> (usize, usize) {
+    use std::ops::Bound;
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => len,
+    };
+    assert!(l <= r && r <= len);
+    (l, r)
+}
@@ -0,0 +1,270 @@
+use crate::complex::EPS;
+use cargo_snippet::snippet;
+#[snippet("Vec3")]
+use std::ops::*;
+
+#[snippet(name = "Vec3", include = "Complex")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[snippet("Vec3")]
+impl Vec3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn dot(&self, rhs: Self) -> f64 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(&self, rhs: Self) -> Self {
+        Vec3::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(*self)
+    }
+
+    pub fn abs(&self) -> f64 {
+        self.norm().sqrt()
+    }
+
+    pub fn unit(&self) -> Self {
+        *self / self.abs()
+    }
+
+    /// Distance to the line through `a` and `b`.
+    pub fn distance_to_line(&self, a: Vec3, b: Vec3) -> f64 {
+        (b - a).cross(*self - a).abs() / (b - a).abs()
+    }
+
+    /// Signed distance to the plane through `a`, `b` and `c`,
+    /// positive on the side of `(b - a) x (c - a)`.
+    pub fn distance_to_plane(&self, a: Vec3, b: Vec3, c: Vec3) -> f64 {
+        (b - a).cross(c - a).unit().dot(*self - a)
+    }
+}
+
+#[snippet("Vec3")]
+impl Add for Vec3 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+#[snippet("Vec3")]
+impl Sub for Vec3 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+#[snippet("Vec3")]
+impl Mul<f64> for Vec3 {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+#[snippet("Vec3")]
+impl Div<f64> for Vec3 {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Vec3::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+#[snippet("Vec3")]
+impl Neg for Vec3 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[snippet("Vec3")]
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+#[snippet("Vec3")]
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Incremental 3D convex hull in O(n^2).
+/// Returns triangular faces as indices, counter-clockwise seen from outside.
+/// Returns an empty vector if all points are coplanar.
+#[snippet(name = "convex_hull_3d", include = "Vec3")]
+pub fn convex_hull_3d(ps: &[Vec3]) -> Vec<[usize; 3]> {
+    use std::collections::HashMap;
+
+    let first = |f: &dyn Fn(Vec3) -> bool| (0..ps.len()).find(|&i| f(ps[i]));
+    let i0 = 0;
+    let i1 = match first(&|p| (p - ps[i0]).abs() > EPS) {
+        Some(i) => i,
+        None => return vec![],
+    };
+    let i2 = match first(&|p| (ps[i1] - ps[i0]).cross(p - ps[i0]).abs() > EPS) {
+        Some(i) => i,
+        None => return vec![],
+    };
+    let i3 = match first(&|p| p.distance_to_plane(ps[i0], ps[i1], ps[i2]).abs() > EPS) {
+        Some(i) => i,
+        None => return vec![],
+    };
+
+    let mut faces: Vec<([usize; 3], Vec3)> = vec![];
+    let mut edges: HashMap<(usize, usize), u8> = HashMap::new();
+    let key = |f: &[usize; 3], k: usize| {
+        let (a, b) = (f[k], f[(k + 1) % 3]);
+        (a.min(b), a.max(b))
+    };
+    let push = |faces: &mut Vec<_>, edges: &mut HashMap<_, _>, f: [usize; 3]| {
+        for k in 0..3 {
+            *edges.entry(key(&f, k)).or_insert(0) += 1;
+        }
+        let q = (ps[f[1]] - ps[f[0]]).cross(ps[f[2]] - ps[f[0]]).unit();
+        faces.push((f, q));
+    };
+    let init = [i0, i1, i2, i3];
+    for a in 0..4 {
+        for b in a + 1..4 {
+            for c in b + 1..4 {
+                let (i, j, k, l) = (init[a], init[b], init[c], init[6 - a - b - c]);
+                if ps[l].distance_to_plane(ps[i], ps[j], ps[k]) > 0. {
+                    push(&mut faces, &mut edges, [i, k, j]);
+                } else {
+                    push(&mut faces, &mut edges, [i, j, k]);
+                }
+            }
+        }
+    }
+
+    for i in 0..ps.len() {
+        if init.contains(&i) {
+            continue;
+        }
+        let mut j = 0;
+        while j < faces.len() {
+            let (f, q) = faces[j];
+            if q.dot(ps[i] - ps[f[0]]) > EPS {
+                for k in 0..3 {
+                    *edges.get_mut(&key(&f, k)).unwrap() -= 1;
+                }
+                faces.swap_remove(j);
+            } else {
+                j += 1;
+            }
+        }
+        for j in 0..faces.len() {
+            let f = faces[j].0;
+            for k in 0..3 {
+                if edges[&key(&f, k)] == 1 {
+                    push(&mut faces, &mut edges, [f[(k + 1) % 3], f[k], i]);
+                }
+            }
+        }
+    }
+    faces.into_iter().map(|(f, _)| f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convex_hull_3d, Vec3};
+
+    fn volume(ps: &[Vec3], faces: &[[usize; 3]]) -> f64 {
+        faces
+            .iter()
+            .map(|f| ps[f[0]].dot(ps[f[1]].cross(ps[f[2]])))
+            .sum::<f64>()
+            / 6.
+    }
+
+    #[test]
+    fn test_distance() {
+        let p = Vec3::new(1., 2., 3.);
+        let (o, x, y) = (
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+        );
+        assert!((p.distance_to_plane(o, x, y) - 3.).abs() < 1e-9);
+        assert!((p.distance_to_plane(o, y, x) + 3.).abs() < 1e-9);
+        assert!((p.distance_to_line(o, x) - 13f64.sqrt()).abs() < 1e-9);
+        assert_eq!(x.cross(y), Vec3::new(0., 0., 1.));
+    }
+
+    #[test]
+    fn test_convex_hull_3d_cube() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        let mut ps = vec![];
+        for i in 0..100 {
+            ps.push(Vec3::new(rng.gen(), rng.gen(), rng.gen()));
+            if i % 10 == 0 {
+                ps.push(Vec3::new(
+                    (i / 10 % 2) as f64,
+                    (i / 20 % 2) as f64,
+                    (i / 40 % 2) as f64,
+                ));
+            }
+        }
+        for i in 0..8 {
+            ps.push(Vec3::new(
+                (i % 2) as f64,
+                (i / 2 % 2) as f64,
+                (i / 4) as f64,
+            ));
+        }
+        let faces = convex_hull_3d(&ps);
+        assert_eq!(faces.len(), 12);
+        assert!((volume(&ps, &faces) - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convex_hull_3d_sphere() {
+        use rand::prelude::*;
+        let mut rng = thread_rng();
+        let ps = (0..200)
+            .map(|_| {
+                Vec3::new(
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                )
+                .unit()
+                    * 10.
+            })
+            .collect::<Vec<_>>();
+        let faces = convex_hull_3d(&ps);
+        assert_eq!(faces.len(), 2 * ps.len() - 4);
+        for f in &faces {
+            for &p in &ps {
+                assert!(p.distance_to_plane(ps[f[0]], ps[f[1]], ps[f[2]]) < 1e-9);
+            }
+        }
+        assert!(volume(&ps, &faces) > 0.);
+        assert!(convex_hull_3d(&[
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1., 1., 0.),
+            Vec3::new(2., 0., 0.)
+        ])
+        .is_empty());
+    }
+}